@@ -154,6 +154,15 @@ fn run_dataset_benchmarks(c: &mut Criterion, dataset_name: &str, dataset: &Datas
             black_box(builder.bytes_len())
         })
     });
+    insert_group.bench_function("extend_from_iter", |b| {
+        b.iter(|| {
+            let mut builder = StringTableBuilder::<u32>::new();
+            builder
+                .extend_from_iter(entries)
+                .expect("failed to insert benchmark paths");
+            black_box(builder.bytes_len())
+        })
+    });
     insert_group.bench_function("preallocated_buffer", |b| {
         b.iter(|| {
             let mut builder = StringTableBuilder::<u32>::with_capacity(string_count, total_bytes);
@@ -188,6 +197,26 @@ fn run_dataset_benchmarks(c: &mut Criterion, dataset_name: &str, dataset: &Datas
             BatchSize::LargeInput,
         )
     });
+    build_group.bench_function("shrunk_before_build", |b| {
+        b.iter_batched(
+            || {
+                let mut builder =
+                    StringTableBuilder::<u32>::with_capacity(string_count, total_bytes);
+                for value in entries {
+                    builder
+                        .try_push(value)
+                        .expect("failed to insert benchmark path");
+                }
+                builder.shrink_to_fit();
+                builder
+            },
+            |builder| {
+                let table = builder.build();
+                black_box(table.as_bytes().len())
+            },
+            BatchSize::LargeInput,
+        )
+    });
     build_group.finish();
 
     let mut construct_group_null_padded =
@@ -211,6 +240,7 @@ fn run_dataset_benchmarks(c: &mut Criterion, dataset_name: &str, dataset: &Datas
     construct_group_null_padded.finish();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn bench_get_group<F>(
     c: &mut Criterion,
     dataset_name: &str,
@@ -292,6 +322,7 @@ fn bench_get_group<F>(
     group.finish();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn bench_get_unchecked_group<F>(
     c: &mut Criterion,
     dataset_name: &str,
@@ -369,6 +400,7 @@ fn bench_get_unchecked_group<F>(
     group.finish();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn bench_iter_group<F>(
     c: &mut Criterion,
     dataset_name: &str,
@@ -416,6 +448,21 @@ fn bench_iter_group<F>(
             black_box(checksum)
         })
     });
+    // Reference loop reading both offsets per string, for comparison with
+    // the iterator's cached start offset.
+    group.bench_function(
+        format!("lite_strtab_index_loop{benchmark_name_suffix}"),
+        |b| {
+            b.iter(|| {
+                let mut checksum = 0usize;
+                for index in 0..table.len() {
+                    let value = table.get_by_index(index).unwrap();
+                    checksum = checksum.wrapping_add(observe(value));
+                }
+                black_box(checksum)
+            })
+        },
+    );
     if let Some(table_null_padded) = table_null_padded {
         group.bench_function(
             format!("lite_strtab_iter{benchmark_name_suffix}_null_padded"),
@@ -0,0 +1,62 @@
+//! Allocator adapter that raises the alignment of every allocation.
+//!
+//! Used by [`crate::StringTableBuilder::build_aligned`] to give the byte
+//! buffer a SIMD-friendly alignment. Since the adapter raises alignment on
+//! both allocation and deallocation, buffers can be freed through plain
+//! `Box<[u8], _>` handles without a layout mismatch.
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+use crate::allocator::{AllocError, Allocator};
+
+/// [`Allocator`] that aligns every allocation to at least `ALIGN` bytes.
+///
+/// `ALIGN` must be a power of two; this is checked at compile time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AlignedAllocator<A, const ALIGN: usize>(A);
+
+impl<A, const ALIGN: usize> AlignedAllocator<A, ALIGN> {
+    /// Wraps `inner` so its allocations are `ALIGN`-aligned.
+    #[inline]
+    pub const fn new(inner: A) -> Self {
+        const { assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two") };
+        Self(inner)
+    }
+
+    /// Returns the wrapped allocator.
+    #[inline]
+    pub fn inner(&self) -> &A {
+        &self.0
+    }
+
+    #[inline]
+    fn aligned(layout: Layout) -> Result<Layout, AllocError> {
+        layout.align_to(ALIGN).map_err(|_| AllocError)
+    }
+}
+
+// SAFETY: Every request is forwarded to the inner allocator with the same
+// size and a raised alignment. Deallocation raises alignment the same way,
+// so the inner allocator always sees matching layouts.
+unsafe impl<A: Allocator, const ALIGN: usize> Allocator for AlignedAllocator<A, ALIGN> {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate(Self::aligned(layout)?)
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate_zeroed(Self::aligned(layout)?)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: `layout` was accepted by `allocate`, so raising its
+        // alignment succeeds again and reproduces the inner layout.
+        unsafe {
+            self.0
+                .deallocate(ptr, Self::aligned(layout).unwrap_unchecked())
+        }
+    }
+}
@@ -6,15 +6,19 @@
 //! unstable allocator API in [`alloc`].
 
 #[cfg(not(feature = "nightly"))]
-pub use allocator_api2::alloc::{Allocator, Global};
+pub use allocator_api2::alloc::{AllocError, Allocator, Global};
 #[cfg(not(feature = "nightly"))]
 pub use allocator_api2::boxed::Box;
 #[cfg(not(feature = "nightly"))]
+pub use allocator_api2::collections::TryReserveError;
+#[cfg(not(feature = "nightly"))]
 pub use allocator_api2::vec::Vec;
 
 #[cfg(feature = "nightly")]
-pub use crate::alloc::alloc::{Allocator, Global};
+pub use crate::alloc::alloc::{AllocError, Allocator, Global};
 #[cfg(feature = "nightly")]
 pub use crate::alloc::boxed::Box;
 #[cfg(feature = "nightly")]
+pub use crate::alloc::collections::TryReserveError;
+#[cfg(feature = "nightly")]
 pub use crate::alloc::vec::Vec;
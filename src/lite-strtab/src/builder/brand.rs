@@ -0,0 +1,27 @@
+//! Pushing strings that remember their table.
+
+use crate::allocator::*;
+use crate::{BrandedStringId, Offset, Result, StringIndex, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Appends a string and returns a [`BrandedStringId`] tied to the table
+    /// this builder produces.
+    ///
+    /// Look it up with
+    /// [`StringTable::get_branded`](crate::StringTable::get_branded), which
+    /// in debug builds panics if given an ID from another table. Returns the
+    /// same errors as [`Self::try_push`].
+    #[inline]
+    #[must_use = "the returned ID is needed to look the string up"]
+    pub fn try_push_branded(&mut self, value: &str) -> Result<BrandedStringId<I>> {
+        let id = self.try_push(value)?;
+        Ok(BrandedStringId::new(id, self.tag))
+    }
+}
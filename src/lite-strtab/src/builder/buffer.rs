@@ -0,0 +1,212 @@
+//! Building into a caller-provided byte buffer.
+
+use core::marker::PhantomData;
+
+use crate::allocator::*;
+use crate::{
+    BorrowedStringTable, Error, Offset, Result, StringId, StringIndex, StringTableBuilder,
+};
+
+/// Builder that writes string bytes into a caller-provided buffer.
+///
+/// Unlike [`StringTableBuilder`], string data never lives in a growable
+/// vector: it is written straight into the buffer, so building into memory
+/// that is already allocated (e.g. a memory-mapped output file) needs no
+/// final copy. Offsets are still kept in a vector in `A`.
+///
+/// Generic parameters match [`StringTableBuilder`].
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::{Error, StringTableBuilder};
+///
+/// let mut buffer = [0u8; 8];
+/// let mut builder = StringTableBuilder::new_in_buffer(&mut buffer);
+/// let hello = builder.try_push("hello").unwrap();
+/// assert!(matches!(
+///     builder.try_push("world"),
+///     Err(Error::ByteLimitExceeded { bytes: 10, limit: 8 })
+/// ));
+///
+/// let table = builder.build();
+/// assert_eq!(table.get(hello), Some("hello"));
+/// assert_eq!(table.as_bytes(), b"hello");
+/// ```
+pub struct BufferStringTableBuilder<
+    'a,
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    buffer: &'a mut [u8],
+    len: usize,
+    offsets: Vec<O, A>,
+    _id: PhantomData<I>,
+}
+
+impl StringTableBuilder<u32, u16, false, Global> {
+    /// Creates a builder that writes string bytes into `buffer`, using the
+    /// global allocator for offsets.
+    ///
+    /// See [`BufferStringTableBuilder`].
+    #[inline]
+    pub fn new_in_buffer(buffer: &mut [u8]) -> BufferStringTableBuilder<'_> {
+        BufferStringTableBuilder::new_in(buffer, Global)
+    }
+}
+
+impl<
+        'a,
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > BufferStringTableBuilder<'a, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates a builder that writes string bytes into `buffer`, with offsets
+    /// allocated in `allocator`.
+    ///
+    /// Existing contents of `buffer` are overwritten as strings are pushed.
+    pub fn new_in(buffer: &'a mut [u8], allocator: A) -> Self {
+        let mut offsets = Vec::with_capacity_in(1, allocator);
+        // SAFETY: All built-in integer implementations accept zero.
+        offsets.push(unsafe { O::try_from_usize(0).unwrap_unchecked() });
+
+        Self {
+            buffer,
+            len: 0,
+            offsets,
+            _id: PhantomData,
+        }
+    }
+
+    /// Number of strings pushed so far.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns `true` when no strings were pushed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of buffer bytes written so far.
+    #[inline]
+    #[must_use]
+    pub fn bytes_len(&self) -> usize {
+        self.len
+    }
+
+    /// Total size of the buffer.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Appends a string and returns its [`StringId`].
+    ///
+    /// Returns [`Error::ByteLimitExceeded`] with the buffer size as the limit
+    /// when the string does not fit in the remaining buffer, and otherwise the
+    /// same errors as [`StringTableBuilder::try_push`]. On error, nothing is
+    /// written.
+    #[must_use = "the returned ID is needed to look the string up"]
+    pub fn try_push(&mut self, value: &str) -> Result<StringId<I>> {
+        let id = self.len();
+        let id = I::try_from_usize(id).ok_or(Error::TooManyStrings {
+            strings: id.saturating_add(1),
+            id_type: I::TYPE_NAME,
+        })?;
+
+        let start = self.len;
+        // Cannot overflow: `start` and `value.len()` both index into memory.
+        let end = start + value.len() + usize::from(NULL_PADDED);
+        if end > self.buffer.len() {
+            return Err(Error::ByteLimitExceeded {
+                bytes: end,
+                limit: self.buffer.len(),
+            });
+        }
+        let end_offset = O::try_from_usize(end).ok_or(Error::TooManyBytesForOffsetType {
+            bytes: end,
+            offset_type: O::TYPE_NAME,
+        })?;
+
+        self.buffer[start..start + value.len()].copy_from_slice(value.as_bytes());
+        if NULL_PADDED {
+            self.buffer[end - 1] = TERMINATOR;
+        }
+        self.offsets.push(end_offset);
+        self.len = end;
+        Ok(StringId::new(id))
+    }
+
+    /// Finalizes into a [`BorrowedStringTable`] over the written part of the
+    /// buffer.
+    #[must_use]
+    pub fn build(self) -> BorrowedStringTable<'a, O, I, NULL_PADDED, A, TERMINATOR> {
+        let buffer: &'a [u8] = self.buffer;
+        BorrowedStringTable::from_parts_unchecked(
+            &buffer[..self.len],
+            self.offsets.into_boxed_slice(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{BufferStringTableBuilder, Error, StringId};
+
+    #[test]
+    fn writes_into_buffer_until_full() {
+        let mut buffer = [0xFFu8; 12];
+        let mut builder = BufferStringTableBuilder::<u8, u8, true>::new_in(&mut buffer, Global);
+        builder.try_push("cat").unwrap();
+        builder.try_push("").unwrap();
+        assert_eq!(builder.bytes_len(), 5);
+
+        assert_eq!(
+            builder.try_push("too long"),
+            Err(Error::ByteLimitExceeded {
+                bytes: 14,
+                limit: 12,
+            })
+        );
+        assert_eq!(builder.try_push("dog").unwrap(), StringId::new(2));
+        assert_eq!(builder.len(), 3);
+
+        let table = builder.build();
+        assert_eq!(table.as_bytes(), b"cat\0\0dog\0");
+        assert_eq!(table.offsets(), &[0u8, 4, 5, 9]);
+        assert!(table.iter().eq(["cat", "", "dog"]));
+        assert_eq!(table.get(StringId::new(2)), Some("dog"));
+        assert_eq!(table.get_by_index(3), None);
+        assert_eq!(buffer[9..], [0xFF; 3]);
+    }
+
+    #[test]
+    fn reports_small_id_type() {
+        let mut buffer = [0u8; 4];
+        let mut builder = BufferStringTableBuilder::<u32, u8>::new_in(&mut buffer, Global);
+        for _ in 0..256 {
+            builder.try_push("").unwrap();
+        }
+        assert!(matches!(
+            builder.try_push(""),
+            Err(Error::TooManyStrings { strings: 257, .. })
+        ));
+        assert_eq!(builder.build().len(), 256);
+    }
+}
@@ -0,0 +1,227 @@
+//! Sizing a builder's buffers.
+
+use core::marker::PhantomData;
+
+use super::zero_offset;
+use crate::allocator::*;
+use crate::types::TableTag;
+use crate::{Error, Offset, Result, StringIndex, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates a builder with reserved capacities and a custom allocator.
+    ///
+    /// `strings` is the expected number of strings, `bytes` is the expected
+    /// total number of UTF-8 bytes. Both are clamped to what `I` and `O` can
+    /// index, so a small `O` such as [`u8`] never reserves more than 255
+    /// bytes. Use [`Self::with_capacity_checked_in`] to get an error instead.
+    pub fn with_capacity_in(strings: usize, bytes: usize, allocator: A) -> Self {
+        let strings = clamp_to(strings, |strings| {
+            strings == 0 || I::try_from_usize(strings - 1).is_some()
+        });
+        let bytes = clamp_to(bytes, |bytes| O::try_from_usize(bytes).is_some());
+        let mut offsets = Vec::with_capacity_in(strings.saturating_add(1), allocator.clone());
+        offsets.push(zero_offset::<O>());
+
+        Self {
+            bytes: Vec::with_capacity_in(bytes, allocator),
+            offsets,
+            byte_limit: usize::MAX,
+            tag: TableTag::new(),
+            _id: PhantomData,
+        }
+    }
+
+    /// Like [`Self::with_capacity_in`], but rejects capacities that the
+    /// offset or ID type cannot hold.
+    ///
+    /// Returns [`Error::TooManyStrings`] if `strings` strings cannot all be
+    /// given an ID of type `I`, and [`Error::TooManyBytesForOffsetType`] if
+    /// `bytes` does not fit in `O`. This reports type parameters that are too
+    /// small before anything is pushed, instead of on the first push past the
+    /// limit. Nothing is allocated on error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{Error, Global, StringTableBuilder};
+    ///
+    /// let result = StringTableBuilder::<u32, u8>::with_capacity_checked_in(1000, 0, Global);
+    /// assert!(matches!(result, Err(Error::TooManyStrings { strings: 1000, .. })));
+    /// ```
+    pub fn with_capacity_checked_in(strings: usize, bytes: usize, allocator: A) -> Result<Self> {
+        if strings > 0 && I::try_from_usize(strings - 1).is_none() {
+            return Err(Error::TooManyStrings {
+                strings,
+                id_type: I::TYPE_NAME,
+            });
+        }
+        if O::try_from_usize(bytes).is_none() {
+            return Err(Error::TooManyBytesForOffsetType {
+                bytes,
+                offset_type: O::TYPE_NAME,
+            });
+        }
+        Ok(Self::with_capacity_in(strings, bytes, allocator))
+    }
+
+    /// Creates a builder sized from an average string length with a custom
+    /// allocator.
+    ///
+    /// Reserves room for `strings` strings and `strings * avg_bytes_per_string`
+    /// bytes (plus one NUL per string in null-padded mode). This is only a
+    /// hint: the buffers still grow if the estimate is exceeded.
+    #[inline]
+    pub fn with_estimated_in(strings: usize, avg_bytes_per_string: usize, allocator: A) -> Self {
+        let per_string = avg_bytes_per_string.saturating_add(usize::from(NULL_PADDED));
+        Self::with_capacity_in(strings, strings.saturating_mul(per_string), allocator)
+    }
+
+    /// Reserves room for at least `strings` more strings totalling `bytes`
+    /// more bytes, returning an error instead of aborting if allocation fails.
+    ///
+    /// In null-padded mode, `bytes` should include one NUL terminator per
+    /// string. On error, the builder is unchanged apart from any capacity
+    /// already reserved.
+    pub fn try_reserve(
+        &mut self,
+        strings: usize,
+        bytes: usize,
+    ) -> core::result::Result<(), TryReserveError> {
+        self.offsets.try_reserve(strings)?;
+        self.bytes.try_reserve(bytes)
+    }
+
+    /// Shrinks the byte buffer and offsets to their lengths.
+    ///
+    /// [`Self::build`] converts both vectors into boxed slices, which
+    /// reallocates any vector with spare capacity. Calling this first moves
+    /// that reallocation here, so a later `build` is a plain pointer move. Use
+    /// it to do the copy at a convenient time; it does not avoid the copy
+    /// itself unless capacity already matches length.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.bytes.shrink_to_fit();
+        self.offsets.shrink_to_fit();
+    }
+}
+
+/// Returns the largest value up to `limit` accepted by `fits`, which must
+/// accept every value from zero up to some maximum.
+fn clamp_to(limit: usize, fits: impl Fn(usize) -> bool) -> usize {
+    if fits(limit) {
+        return limit;
+    }
+
+    // Invariant: `fits(low)` and `!fits(high)`.
+    let (mut low, mut high) = (0, limit);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU8;
+
+    use crate::allocator::Global;
+    use crate::{Error, StringTableBuilder};
+
+    #[test]
+    fn with_capacity_clamps_to_small_types() {
+        let builder = StringTableBuilder::<u8, u8>::with_capacity_in(1 << 20, 1 << 20, Global);
+        assert!((255..1 << 20).contains(&builder.bytes.capacity()));
+        assert!((257..1 << 20).contains(&builder.offsets.capacity()));
+
+        let builder =
+            StringTableBuilder::<u32, NonZeroU8>::with_capacity_in(usize::MAX, 16, Global);
+        assert!((256..1 << 20).contains(&builder.offsets.capacity()));
+        assert!(builder.bytes.capacity() >= 16);
+    }
+
+    #[test]
+    fn with_capacity_checked_rejects_small_types() {
+        let Ok(builder) = StringTableBuilder::<u8, u8>::with_capacity_checked_in(256, 255, Global)
+        else {
+            panic!("256 strings and 255 bytes fit u8 IDs and offsets");
+        };
+        assert_eq!(builder.offsets.capacity(), 257);
+
+        let Err(error) = StringTableBuilder::<u8, u8>::with_capacity_checked_in(257, 0, Global)
+        else {
+            panic!("expected too many strings");
+        };
+        assert_eq!(
+            error,
+            Error::TooManyStrings {
+                strings: 257,
+                id_type: "u8",
+            }
+        );
+
+        let Err(error) = StringTableBuilder::<u8, u8>::with_capacity_checked_in(1, 256, Global)
+        else {
+            panic!("expected too many bytes");
+        };
+        assert_eq!(
+            error,
+            Error::TooManyBytesForOffsetType {
+                bytes: 256,
+                offset_type: "u8",
+            }
+        );
+        assert!(StringTableBuilder::with_capacity_checked(0, 0).is_ok());
+    }
+
+    #[test]
+    fn with_estimated_reserves_from_average() {
+        let builder = StringTableBuilder::with_estimated(10, 8);
+        assert!(builder.offsets.capacity() >= 11);
+        assert!(builder.bytes.capacity() >= 80);
+
+        let builder = StringTableBuilder::<u32, u16, true>::with_estimated_in(10, 8, Global);
+        assert!(builder.bytes.capacity() >= 90);
+
+        let mut builder = StringTableBuilder::with_estimated(1, 1);
+        builder.try_push("grows past the estimate").unwrap();
+        assert_eq!(builder.len(), 1);
+    }
+
+    #[test]
+    fn try_reserve_grows_both_buffers() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_reserve(8, 64).unwrap();
+        assert!(builder.offsets.capacity() >= 9);
+        assert!(builder.bytes.capacity() >= 64);
+
+        assert!(builder.try_reserve(0, usize::MAX).is_err());
+        builder.try_push("still usable").unwrap();
+        assert_eq!(builder.build().get_by_index(0), Some("still usable"));
+    }
+
+    #[test]
+    fn shrink_to_fit_drops_spare_capacity() {
+        let mut builder = StringTableBuilder::with_capacity(16, 256);
+        builder.try_push("hello").unwrap();
+        builder.shrink_to_fit();
+        assert_eq!(builder.bytes.capacity(), builder.bytes.len());
+        assert_eq!(builder.offsets.capacity(), builder.offsets.len());
+
+        let bytes_ptr = builder.bytes.as_ptr();
+        let table = builder.build();
+        assert_eq!(table.as_bytes().as_ptr(), bytes_ptr);
+        assert_eq!(table.get_by_index(0), Some("hello"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Undoing speculative pushes.
+
+use crate::allocator::*;
+use crate::{Offset, StringIndex, StringTableBuilder};
+
+/// Builder state captured by [`StringTableBuilder::checkpoint`].
+///
+/// Pass it to [`StringTableBuilder::rollback`] to discard strings pushed
+/// since.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    bytes: usize,
+    offsets: usize,
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Captures the current length so later pushes can be undone with
+    /// [`Self::rollback`].
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            bytes: self.bytes.len(),
+            offsets: self.offsets.len(),
+        }
+    }
+
+    /// Discards every string pushed since `checkpoint` was taken.
+    ///
+    /// IDs handed out after the checkpoint become invalid and will be reused.
+    /// Capacity is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `checkpoint` does not describe an earlier state of this
+    /// builder, for example because it was taken before [`Self::clear`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("kept").unwrap();
+    /// let checkpoint = builder.checkpoint();
+    /// builder.try_push("speculative").unwrap();
+    ///
+    /// builder.rollback(checkpoint);
+    /// assert!(builder.build().eq_strings(["kept"]));
+    /// ```
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let Checkpoint { bytes, offsets } = checkpoint;
+        assert!(
+            offsets <= self.offsets.len() && self.offsets[offsets - 1].to_usize() == bytes,
+            "checkpoint does not match this builder"
+        );
+        self.bytes.truncate(bytes);
+        self.offsets.truncate(offsets);
+    }
+
+    /// Removes the most recently pushed string, returning `false` when the
+    /// builder is empty.
+    ///
+    /// In null-padded mode the terminator byte is removed too. The freed ID
+    /// and bytes are reused by the next push; capacity is kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("kept").unwrap();
+    /// builder.try_push("invalid").unwrap();
+    ///
+    /// assert!(builder.pop());
+    /// assert!(builder.build().eq_strings(["kept"]));
+    /// ```
+    pub fn pop(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        self.offsets.pop();
+        let start = self.offsets[self.offsets.len() - 1].to_usize();
+        self.bytes.truncate(start);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn rollback_discards_pushes_after_checkpoint() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("a").unwrap();
+        let checkpoint = builder.checkpoint();
+        builder.try_push("bb").unwrap();
+        builder.try_push("").unwrap();
+
+        builder.rollback(checkpoint);
+        assert_eq!(builder.len(), 1);
+        assert_eq!(builder.bytes_len(), 2);
+        assert_eq!(builder.try_push("c").unwrap(), StringId::new(1));
+
+        let table = builder.build();
+        assert!(table.iter().eq(["a", "c"]));
+        assert_eq!(table.as_bytes(), b"a\0c\0");
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint does not match this builder")]
+    fn rollback_rejects_stale_checkpoint() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("abc").unwrap();
+        let checkpoint = builder.checkpoint();
+        builder.clear();
+        builder.try_push("x").unwrap();
+        builder.try_push("y").unwrap();
+        builder.rollback(checkpoint);
+    }
+
+    #[test]
+    fn pop_removes_last_string_and_terminator() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("a").unwrap();
+        builder.try_push("bc").unwrap();
+
+        assert!(builder.pop());
+        assert_eq!(builder.len(), 1);
+        assert_eq!(builder.bytes(), b"a\0");
+        assert_eq!(builder.try_push("d").unwrap(), StringId::new(1));
+
+        assert!(builder.pop());
+        assert!(builder.pop());
+        assert!(!builder.pop());
+        assert!(builder.is_empty());
+        assert_eq!(builder.offsets(), &[0u32]);
+        assert!(builder.bytes().is_empty());
+    }
+}
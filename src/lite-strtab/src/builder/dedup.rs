@@ -0,0 +1,214 @@
+//! Collapsing repeated or equivalent strings.
+
+use core::hash::{BuildHasher, Hash};
+
+use hashbrown::{DefaultHashBuilder, HashMap, HashTable};
+
+use crate::allocator::*;
+use crate::{Offset, Result, StringId, StringIndex, StringTable, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Removes repeated strings in place, keeping the first occurrence of each.
+    ///
+    /// Surviving strings keep their relative order and are renumbered densely.
+    /// Returns a remap indexed by the old IDs: `remap[old]` is the new ID of
+    /// the string `old` referred to.
+    ///
+    /// Runs in expected `O(n)` time over the byte buffer. Besides the returned
+    /// remap, it allocates a temporary hash table with one entry per distinct
+    /// string; the byte buffer and offsets are compacted without reallocating.
+    pub fn dedup(&mut self) -> Box<[StringId<I>], A> {
+        let len = self.len();
+        let hasher = DefaultHashBuilder::default();
+        let mut seen: HashTable<usize> = HashTable::with_capacity(len);
+        let mut remap = Vec::with_capacity_in(len, self.bytes.allocator().clone());
+
+        // Survivors only move towards the front, so the first `kept` strings
+        // of `bytes`/`offsets` are always final and can be used for lookups.
+        // Old offsets are read before the slot they occupy is overwritten.
+        let mut kept = 0;
+        let mut read_start = 0;
+        for old in 0..len {
+            let read_end = self.offsets[old + 1].to_usize();
+            let value = &self.bytes[read_start..read_end];
+            let hash = hasher.hash_one(value);
+            let found = seen
+                .find(hash, |&index| {
+                    stored(&self.bytes, &self.offsets, index) == value
+                })
+                .copied();
+
+            let index = found.unwrap_or_else(|| {
+                let write_start = self.offsets[kept].to_usize();
+                let write_end = write_start + (read_end - read_start);
+                self.bytes.copy_within(read_start..read_end, write_start);
+                // SAFETY: `write_end <= read_end`, which already fits in `O`.
+                self.offsets[kept + 1] = unsafe { O::try_from_usize(write_end).unwrap_unchecked() };
+                let (bytes, offsets) = (&self.bytes, &self.offsets);
+                seen.insert_unique(hash, kept, |&index| {
+                    hasher.hash_one(stored(bytes, offsets, index))
+                });
+                kept += 1;
+                kept - 1
+            });
+            remap.push(StringTable::<O, I, NULL_PADDED, A, TERMINATOR>::id_at(
+                index,
+            ));
+            read_start = read_end;
+        }
+
+        let bytes_len = self.offsets[kept].to_usize();
+        self.bytes.truncate(bytes_len);
+        self.offsets.truncate(kept + 1);
+        remap.into_boxed_slice()
+    }
+
+    /// Finalizes into a table that keeps only the first string for each key.
+    ///
+    /// `key` maps each string to the value used to detect duplicates; for
+    /// example, [`str::to_ascii_lowercase`] collapses entries that differ only
+    /// in ASCII case. Surviving strings receive new, dense IDs in their
+    /// original order.
+    ///
+    /// Also returns a remap indexed by the builder's IDs: `remap[old]` is the
+    /// ID in the new table of the string that `old` collapsed into.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("Foo").unwrap();
+    /// builder.try_push("bar").unwrap();
+    /// let foo = builder.try_push("foo").unwrap();
+    ///
+    /// let (table, remap) = builder.build_dedup_by(str::to_ascii_lowercase);
+    /// assert_eq!(table.len(), 2);
+    /// assert_eq!(table.get(remap[foo.into_usize()]), Some("Foo"));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn build_dedup_by<K: Hash + Eq, F: FnMut(&str) -> K>(
+        self,
+        mut key: F,
+    ) -> (
+        StringTable<O, I, NULL_PADDED, A, TERMINATOR>,
+        Box<[StringId<I>], A>,
+    ) {
+        let allocator = self.bytes.allocator().clone();
+        let mut survivors: HashMap<K, StringId<I>> = HashMap::with_capacity(self.len());
+        let mut remap = Vec::with_capacity_in(self.len(), allocator.clone());
+        let mut deduped = Self::with_capacity_in(self.len(), self.bytes_len(), allocator);
+
+        for index in 0..self.len() {
+            let value = self.str_at(index);
+            let id = *survivors.entry(key(value)).or_insert_with(|| {
+                // Cannot fail: survivors are a subset of strings that already
+                // fit in `O` and `I`.
+                deduped.try_push(value).unwrap()
+            });
+            remap.push(id);
+        }
+
+        (deduped.build(), remap.into_boxed_slice())
+    }
+
+    /// Returns the ID of `value` if it was already pushed, and otherwise
+    /// appends it.
+    ///
+    /// Finds existing strings by scanning every pushed string, so no index
+    /// is kept between calls, but pushing `n` strings this way takes
+    /// `O(n²)` time in the worst case. Use it for small builders; for large
+    /// inputs prefer [`StringInterner::try_push_interned`].
+    ///
+    /// Returns the same errors as [`Self::try_push`].
+    ///
+    /// [`StringInterner::try_push_interned`]: crate::StringInterner::try_push_interned
+    #[doc(alias = "push_if_absent")]
+    pub fn try_push_if_absent(&mut self, value: &str) -> Result<StringId<I>> {
+        match (0..self.len()).find(|&index| self.str_at(index) == value) {
+            Some(index) => Ok(StringTable::<O, I, NULL_PADDED, A, TERMINATOR>::id_at(
+                index,
+            )),
+            None => self.try_push(value),
+        }
+    }
+}
+
+/// Returns the stored bytes of string `index`, including any NUL terminator.
+#[inline]
+fn stored<'a, O: Offset>(bytes: &'a [u8], offsets: &[O], index: usize) -> &'a [u8] {
+    &bytes[offsets[index].to_usize()..offsets[index + 1].to_usize()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn build_dedup_by_collapses_case_insensitive_duplicates() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["Foo", "bar", "foo", "BAR", "baz", "FOO"] {
+            builder.try_push(value).unwrap();
+        }
+
+        let (table, remap) = builder.build_dedup_by(str::to_ascii_lowercase);
+        assert!(table.iter().eq(["Foo", "bar", "baz"]));
+        assert_eq!(table.as_bytes(), b"Foo\0bar\0baz\0");
+        assert_eq!(&*remap, [0, 1, 0, 1, 2, 0].map(StringId::<u16>::new));
+    }
+
+    #[test]
+    fn dedup_keeps_first_occurrences_in_place() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["b", "a", "b", "", "a", "", "c"] {
+            builder.try_push(value).unwrap();
+        }
+        let capacity = builder.bytes.capacity();
+
+        let remap = builder.dedup();
+        assert_eq!(&*remap, [0, 1, 0, 2, 1, 2, 3].map(StringId::<u16>::new));
+        assert_eq!(builder.bytes.capacity(), capacity);
+
+        let id = builder.try_push("d").unwrap();
+        assert_eq!(id, StringId::new(4));
+        let table = builder.build();
+        assert!(table.iter().eq(["b", "a", "", "c", "d"]));
+        assert_eq!(table.as_bytes(), b"b\0a\0\0c\0d\0");
+    }
+
+    #[test]
+    fn dedup_without_duplicates_is_identity() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("x").unwrap();
+        builder.try_push("y").unwrap();
+        assert_eq!(&*builder.dedup(), [StringId::new(0), StringId::new(1)]);
+        assert!(StringTableBuilder::new().dedup().is_empty());
+    }
+
+    #[test]
+    fn try_push_if_absent_reuses_existing_ids() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let a = builder.try_push_if_absent("a").unwrap();
+        let empty = builder.try_push_if_absent("").unwrap();
+        assert_eq!(builder.try_push_if_absent("a").unwrap(), a);
+        assert_eq!(builder.try_push_if_absent("").unwrap(), empty);
+        assert_eq!(builder.try_push_if_absent("ab").unwrap(), StringId::new(2));
+
+        assert!(builder.build().iter().eq(["a", "", "ab"]));
+    }
+
+    #[test]
+    fn build_dedup_by_on_empty_builder() {
+        let (table, remap) = StringTableBuilder::new().build_dedup_by(str::len);
+        assert!(table.is_empty());
+        assert!(remap.is_empty());
+    }
+}
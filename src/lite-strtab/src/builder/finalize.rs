@@ -0,0 +1,189 @@
+//! Finalizing a builder with extra checks or layout guarantees.
+
+use crate::allocator::*;
+use crate::{
+    AlignedAllocator, Error, Offset, PackedStringTable, Result, StringId, StringIndex, StringTable,
+    StringTableBuilder,
+};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Runs `f` over every string, then finalizes like [`Self::build`].
+    ///
+    /// Use this to enforce domain rules (maximum length, normalization, ...)
+    /// on top of the table's structural invariants. The first error from `f`
+    /// stops validation and is returned together with the unchanged builder,
+    /// so the caller can fix up or inspect its contents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("short").unwrap();
+    /// builder.try_push("much too long").unwrap();
+    ///
+    /// let result =
+    ///     builder.build_validated(|id, value| if value.len() <= 8 { Ok(()) } else { Err(id) });
+    /// let Err((bad, builder)) = result else {
+    ///     panic!("validation should fail");
+    /// };
+    /// assert_eq!(bad.into_usize(), 1);
+    /// assert_eq!(builder.len(), 2);
+    /// ```
+    pub fn build_validated<E, F: FnMut(StringId<I>, &str) -> core::result::Result<(), E>>(
+        self,
+        mut f: F,
+    ) -> core::result::Result<StringTable<O, I, NULL_PADDED, A, TERMINATOR>, (E, Self)> {
+        for index in 0..self.len() {
+            let id = StringTable::<O, I, NULL_PADDED, A, TERMINATOR>::id_at(index);
+            if let Err(error) = f(id, self.str_at(index)) {
+                return Err((error, self));
+            }
+        }
+        Ok(self.build())
+    }
+
+    /// Finalizes into an immutable [`crate::StringTable`] whose byte buffer is
+    /// `ALIGN`-aligned and NUL-padded to a multiple of `ALIGN` bytes.
+    ///
+    /// This lets callers scan [`StringTable::as_bytes`] in whole `ALIGN`-byte
+    /// (e.g. SIMD) chunks without a scalar tail. The padding sits past the
+    /// final sentinel, so offsets and [`StringTable::len`] are unaffected.
+    /// An empty buffer is not allocated and carries no alignment guarantee.
+    ///
+    /// Unlike [`Self::build`], this copies both buffers into
+    /// [`AlignedAllocator`] allocations. Returns an error when the padded
+    /// byte length cannot be represented by the configured offset type.
+    pub fn build_aligned<const ALIGN: usize>(
+        self,
+    ) -> Result<StringTable<O, I, NULL_PADDED, AlignedAllocator<A, ALIGN>, TERMINATOR>> {
+        let padded_len = self.bytes.len().next_multiple_of(ALIGN);
+        if O::try_from_usize(padded_len).is_none() {
+            return Err(Error::TooManyBytesForOffsetType {
+                bytes: padded_len,
+                offset_type: O::TYPE_NAME,
+            });
+        }
+
+        let allocator = AlignedAllocator::new(self.bytes.allocator().clone());
+        let mut bytes = Vec::with_capacity_in(padded_len, allocator.clone());
+        bytes.extend_from_slice(&self.bytes);
+        bytes.resize(padded_len, 0);
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), allocator);
+        offsets.extend_from_slice(&self.offsets);
+
+        let table = StringTable::from_parts_tagged(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+            self.tag,
+        );
+        debug_assert!(table.validate().is_ok());
+        Ok(table)
+    }
+
+    /// Finalizes into a [`PackedStringTable`], with the header, offsets and
+    /// string bytes in one allocation.
+    ///
+    /// The staged buffers are copied once into the packed buffer and then
+    /// freed.
+    #[must_use]
+    pub fn build_packed(self) -> PackedStringTable<O, I, NULL_PADDED, A, TERMINATOR> {
+        let buffer = StringTable::<O, I, NULL_PADDED, A, TERMINATOR>::encode_in(
+            &self.bytes,
+            &self.offsets,
+            self.bytes.allocator().clone(),
+        );
+        PackedStringTable::from_encoded(buffer, self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{Error, StringId, StringTableBuilder};
+
+    #[test]
+    fn build_validated_runs_hook_over_every_string() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("a").unwrap();
+        builder.try_push("bc").unwrap();
+
+        let mut visited = alloc::vec::Vec::new();
+        let Ok(table) = builder.build_validated(|id, value| {
+            visited.push((id, alloc::string::String::from(value)));
+            Ok::<(), ()>(())
+        }) else {
+            panic!("validation should succeed");
+        };
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(visited.len(), 2);
+        assert_eq!(visited[1], (StringId::new(1), "bc".into()));
+    }
+
+    #[test]
+    fn build_validated_returns_builder_on_error() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("ok").unwrap();
+        builder.try_push("").unwrap();
+        builder.try_push("never visited").unwrap();
+
+        let mut calls = 0;
+        let result = builder.build_validated(|id, value| {
+            calls += 1;
+            if value.is_empty() {
+                Err(id)
+            } else {
+                Ok(())
+            }
+        });
+        let Err((error, mut builder)) = result else {
+            panic!("validation should fail");
+        };
+
+        assert_eq!(error, StringId::new(1));
+        assert_eq!(calls, 2);
+        builder.try_push("more").unwrap();
+        assert_eq!(builder.build().len(), 4);
+    }
+
+    #[test]
+    fn build_aligned_pads_and_aligns_bytes() {
+        let mut builder = StringTableBuilder::new();
+        let a = builder.try_push("hello").unwrap();
+        let b = builder.try_push("world!").unwrap();
+        let table = builder.build_aligned::<32>().unwrap();
+
+        assert_eq!(table.as_bytes().as_ptr() as usize % 32, 0);
+        assert_eq!(table.as_bytes().len(), 32);
+        assert_eq!(&table.as_bytes()[..11], b"helloworld!");
+        assert!(table.as_bytes()[11..].iter().all(|&byte| byte == 0));
+        assert_eq!(table.offsets(), &[0u32, 5, 11]);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(a), Some("hello"));
+        assert_eq!(table.get(b), Some("world!"));
+        assert_eq!(table.validate(), Ok(()));
+    }
+
+    #[test]
+    fn build_aligned_reports_padding_overflow() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        builder.try_push(&"a".repeat(250)).unwrap();
+
+        assert!(matches!(
+            builder.build_aligned::<16>(),
+            Err(Error::TooManyBytesForOffsetType {
+                bytes: 256,
+                offset_type: "u8",
+            })
+        ));
+    }
+}
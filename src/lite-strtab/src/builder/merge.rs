@@ -0,0 +1,119 @@
+//! Building tables by merging existing ones.
+
+use core::cmp::Ordering;
+
+use crate::allocator::*;
+use crate::{Offset, Result, StringIndex, StringTable, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Merges two sorted tables into a sorted table holding each distinct
+    /// string once, like a sorted-set union.
+    ///
+    /// Both inputs must be in ascending byte order, as produced by sorting
+    /// with [`str::cmp`]; this is checked with
+    /// [`StringTable::debug_assert_sorted`] in debug builds only. The merge is
+    /// a single pass over both tables, and the result is ready for
+    /// [`StringTable::equal_range`] and other sorted lookups. It is allocated
+    /// with `a`'s allocator.
+    ///
+    /// Returns the same errors as [`Self::try_push`] when the union does not
+    /// fit `O` or `I`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut left = StringTableBuilder::new();
+    /// let mut right = StringTableBuilder::new();
+    /// for value in ["apple", "cherry"] {
+    ///     left.try_push(value).unwrap();
+    /// }
+    /// for value in ["banana", "cherry", "date"] {
+    ///     right.try_push(value).unwrap();
+    /// }
+    ///
+    /// let merged = StringTableBuilder::merge_sorted(&left.build(), &right.build()).unwrap();
+    /// assert!(merged.eq_strings(["apple", "banana", "cherry", "date"]));
+    /// ```
+    pub fn merge_sorted(
+        a: &StringTable<O, I, NULL_PADDED, A, TERMINATOR>,
+        b: &StringTable<O, I, NULL_PADDED, A, TERMINATOR>,
+    ) -> Result<StringTable<O, I, NULL_PADDED, A, TERMINATOR>> {
+        a.debug_assert_sorted();
+        b.debug_assert_sorted();
+
+        let mut builder = Self::with_capacity_in(
+            a.len().saturating_add(b.len()),
+            a.as_bytes().len().saturating_add(b.as_bytes().len()),
+            a.allocator().clone(),
+        );
+        let (mut left, mut right) = (a.iter().peekable(), b.iter().peekable());
+        let mut last = None;
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Ordering::Less => left.next(),
+                    Ordering::Greater => right.next(),
+                    Ordering::Equal => {
+                        right.next();
+                        left.next()
+                    }
+                },
+                (Some(_), None) => left.next(),
+                (None, _) => right.next(),
+            };
+            let Some(value) = next else { break };
+            if last != Some(value) {
+                builder.try_push(value)?;
+                last = Some(value);
+            }
+        }
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{Error, StringTable, StringTableBuilder};
+
+    fn table<const N: usize>(values: [&str; N]) -> StringTable<u8, u8> {
+        let mut builder = StringTableBuilder::new_in(Global);
+        for value in values {
+            builder.try_push(value).unwrap();
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn merge_sorted_drops_duplicates_across_and_within_inputs() {
+        let a = table(["", "a", "a", "c"]);
+        let b = table(["a", "b", "c", "d"]);
+
+        let merged = StringTableBuilder::merge_sorted(&a, &b).unwrap();
+        assert!(merged.eq_strings(["", "a", "b", "c", "d"]));
+        assert!(StringTableBuilder::merge_sorted(&a, &table([]))
+            .unwrap()
+            .eq_strings(["", "a", "c"]));
+    }
+
+    #[test]
+    fn merge_sorted_reports_overflow() {
+        let long = "x".repeat(200);
+        let a = table([long.as_str()]);
+        let b = table([&long[1..]]);
+
+        assert!(matches!(
+            StringTableBuilder::merge_sorted(&a, &b),
+            Err(Error::TooManyBytesForOffsetType { .. })
+        ));
+    }
+}
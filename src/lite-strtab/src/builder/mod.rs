@@ -0,0 +1,788 @@
+//! Builder for creating an immutable [`crate::StringTable`].
+//!
+//! The builder stores data in growable vectors while constructing.
+//! [`StringTableBuilder::build`] converts those vectors to boxed slices,
+//! making the final table immutable and compact.
+
+mod brand;
+mod buffer;
+mod capacity;
+mod checkpoint;
+mod dedup;
+mod extend;
+mod finalize;
+mod merge;
+mod refs;
+#[cfg(feature = "unicode-normalization")]
+mod unicode;
+mod writer;
+
+pub use buffer::BufferStringTableBuilder;
+pub use checkpoint::Checkpoint;
+pub use refs::RefStringTableBuilder;
+pub use writer::BuilderStringWriter;
+
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::allocator::*;
+use crate::types::TableTag;
+use crate::{Error, Offset, Result, StringId, StringIndex, StringTable};
+
+/// Alias for [`StringTableBuilder`].
+pub type StringPoolBuilder<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A = Global,
+    const TERMINATOR: u8 = 0,
+> = StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>;
+
+/// Incremental builder for [`crate::StringTable`].
+///
+/// Each call to [`Self::try_push`] appends string bytes to a single byte buffer and
+/// appends one offset.
+///
+/// Generic parameters control capacity and metadata size:
+/// - `O` is the byte-offset type (see [`Offset`]). It bounds total UTF-8 bytes and costs
+///   `size_of::<O>()` per string inside the [`crate::StringTable`].
+/// - `I` is the string-ID type (see [`StringIndex`]) used by [`crate::StringId`].
+///   It limits string count and costs `size_of::<I>()` per stored ID field
+///   (table index) in your own structs.
+///
+/// The common choice is `O = u32, I = u16`: meaning `4 GiB` of UTF-8 data
+/// and `64Ki` entries per table.
+///
+/// This is 4 bytes per string offset in the table and 2 bytes
+/// per StringID (index into table) inside your structs.
+/// For comparison: `Box<str>` == 16 bytes, `String` == 24 bytes.
+///
+/// By default, inserted strings are not NUL-terminated.
+/// Set `NULL_PADDED = true` to store strings with a trailing `TERMINATOR`
+/// byte, NUL unless configured otherwise.
+pub struct StringTableBuilder<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    pub(crate) bytes: Vec<u8, A>,
+    pub(crate) offsets: Vec<O, A>,
+    byte_limit: usize,
+    tag: TableTag,
+    _id: PhantomData<I>,
+}
+
+/// Result of [`StringTableBuilder::try_push_or_skip`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[must_use]
+pub enum PushOutcome<I = u16> {
+    /// The string was appended with this ID.
+    Pushed(StringId<I>),
+    /// The string was not appended: the ID type cannot index another string.
+    SkippedTooManyStrings,
+    /// The string was not appended: the byte length would exceed the offset
+    /// type or the limit set with [`StringTableBuilder::set_byte_limit`].
+    SkippedTooManyBytes,
+}
+
+impl StringTableBuilder<u32, u16, false, Global> {
+    /// Creates an empty builder using the global allocator.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a builder with reserved capacities using the global allocator.
+    ///
+    /// `strings` is the expected number of strings, `bytes` is the expected
+    /// total number of UTF-8 bytes.
+    #[inline]
+    pub fn with_capacity(strings: usize, bytes: usize) -> Self {
+        Self::with_capacity_in(strings, bytes, Global)
+    }
+
+    /// Like [`Self::with_capacity`], but rejects capacities that the offset
+    /// or ID type cannot hold.
+    ///
+    /// See [`Self::with_capacity_checked_in`].
+    #[inline]
+    pub fn with_capacity_checked(strings: usize, bytes: usize) -> Result<Self> {
+        Self::with_capacity_checked_in(strings, bytes, Global)
+    }
+
+    /// Creates a builder sized from an average string length using the
+    /// global allocator.
+    ///
+    /// See [`Self::with_estimated_in`].
+    #[inline]
+    pub fn with_estimated(strings: usize, avg_bytes_per_string: usize) -> Self {
+        Self::with_estimated_in(strings, avg_bytes_per_string, Global)
+    }
+}
+
+impl Default for StringTableBuilder<u32, u16, false, Global> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StringTableBuilder<u32, u16, true, Global> {
+    /// Creates an empty builder with null-padded mode using the global allocator.
+    #[inline]
+    pub fn new_null_padded() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a null-padded builder with reserved capacities using the global allocator.
+    ///
+    /// `strings` is the expected number of strings, `bytes` is the expected
+    /// total number of UTF-8 bytes.
+    #[inline]
+    pub fn with_capacity_null_padded(strings: usize, bytes: usize) -> Self {
+        Self::with_capacity_in(strings, bytes, Global)
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates an empty builder with a custom allocator.
+    pub fn new_in(allocator: A) -> Self {
+        let mut offsets = Vec::with_capacity_in(1, allocator.clone());
+        offsets.push(zero_offset::<O>());
+
+        Self {
+            bytes: Vec::new_in(allocator),
+            offsets,
+            byte_limit: usize::MAX,
+            tag: TableTag::new(),
+            _id: PhantomData,
+        }
+    }
+
+    /// Caps the total byte length, including NUL terminators, at `limit`.
+    ///
+    /// Pushes that would exceed the limit fail with
+    /// [`Error::ByteLimitExceeded`] and leave the builder unchanged, even when
+    /// `O` could hold more. Use this to enforce size limits of an external
+    /// format. By default there is no limit beyond what `O` can represent.
+    /// Strings already pushed are kept even if they exceed a lower limit.
+    #[inline]
+    pub fn set_byte_limit(&mut self, limit: usize) {
+        self.byte_limit = limit;
+    }
+
+    /// Number of strings currently pushed.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` when the builder has no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current total byte length of pushed string data.
+    #[inline]
+    #[must_use]
+    pub fn bytes_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the bytes pushed so far, including NUL terminators in
+    /// null-padded mode.
+    ///
+    /// Mirrors [`StringTable::as_bytes`] for the table [`Self::build`] would
+    /// produce.
+    #[inline]
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the offsets pushed so far, including the trailing sentinel.
+    ///
+    /// Mirrors [`StringTable::offsets`].
+    #[inline]
+    #[must_use]
+    pub fn offsets(&self) -> &[O] {
+        &self.offsets
+    }
+
+    /// Removes every pushed string, keeping allocated capacity for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.offsets.truncate(1);
+    }
+
+    /// Returns the string at `index`, which must be below [`Self::len`].
+    #[inline]
+    pub(crate) fn str_at(&self, index: usize) -> &str {
+        let start = self.offsets[index].to_usize();
+        // Const generic: default (`false`) folds the subtraction away.
+        let end = self.offsets[index + 1].to_usize() - usize::from(NULL_PADDED);
+        // SAFETY: Bytes are only appended from `&str` values, so every string
+        // range is valid UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[start..end]) }
+    }
+
+    /// Appends a string and returns its [`StringId`].
+    ///
+    /// Returns an error when total string count exceeds the configured ID
+    /// type, or when the byte length cannot be represented by the configured
+    /// offset type.
+    #[must_use = "the returned ID is needed to look the string up"]
+    pub fn try_push(&mut self, value: &str) -> Result<StringId<I>> {
+        let id_value = self.next_id()?;
+
+        let start = self.bytes.len();
+        let end = start
+            .checked_add(value.len())
+            .ok_or(Error::TooManyBytesForOffsetType {
+                bytes: start,
+                offset_type: O::TYPE_NAME,
+            })?;
+        // Branch resolved at compile time; no runtime cost.
+        let end = if NULL_PADDED {
+            end.checked_add(1).ok_or(Error::TooManyBytesForOffsetType {
+                bytes: start,
+                offset_type: O::TYPE_NAME,
+            })?
+        } else {
+            end
+        };
+
+        let end_offset = self.end_offset(end)?;
+
+        self.bytes.extend_from_slice(value.as_bytes());
+        if NULL_PADDED {
+            self.bytes.push(TERMINATOR);
+        }
+        self.offsets.push(end_offset);
+        Ok(StringId::new(id_value))
+    }
+
+    /// Appends a string, or leaves the builder unchanged if it does not fit.
+    ///
+    /// Same as [`Self::try_push`], but reports the outcome as a
+    /// [`PushOutcome`] instead of a [`Result`], for loops that keep going
+    /// after a string is rejected.
+    #[inline]
+    pub fn try_push_or_skip(&mut self, value: &str) -> PushOutcome<I> {
+        match self.try_push(value) {
+            Ok(id) => PushOutcome::Pushed(id),
+            Err(Error::TooManyStrings { .. }) => PushOutcome::SkippedTooManyStrings,
+            // `try_push` otherwise only fails with byte capacity errors.
+            Err(_) => PushOutcome::SkippedTooManyBytes,
+        }
+    }
+
+    /// Appends a string and returns its [`StringId`] together with the byte
+    /// range it occupies.
+    ///
+    /// The range indexes into the final table's
+    /// [`StringTable::as_bytes`](crate::StringTable::as_bytes): building moves
+    /// the buffer without reordering it. In null-padded mode the range excludes
+    /// the NUL terminator, matching
+    /// [`StringTable::byte_range`](crate::StringTable::byte_range).
+    ///
+    /// Returns the same errors as [`Self::try_push`].
+    #[inline]
+    pub fn try_push_with_range(&mut self, value: &str) -> Result<(StringId<I>, Range<usize>)> {
+        let start = self.bytes.len();
+        let id = self.try_push(value)?;
+        Ok((id, start..start + value.len()))
+    }
+
+    /// Appends `parts` joined by `separator` as a single string and returns
+    /// its [`StringId`].
+    ///
+    /// Fragments are written straight into the byte buffer, so no intermediate
+    /// [`String`](alloc::string::String) is allocated. If the joined string
+    /// does not fit the offset type, the builder is left unchanged.
+    ///
+    /// Returns the same errors as [`Self::try_push`].
+    pub fn try_push_joined<S: AsRef<str>, It: IntoIterator<Item = S>>(
+        &mut self,
+        parts: It,
+        separator: &str,
+    ) -> Result<StringId<I>> {
+        let id = self.next_id()?;
+        let start = self.bytes.len();
+        for (index, part) in parts.into_iter().enumerate() {
+            if index != 0 {
+                self.bytes.extend_from_slice(separator.as_bytes());
+            }
+            self.bytes.extend_from_slice(part.as_ref().as_bytes());
+        }
+        self.try_commit(start, id)
+    }
+
+    /// Appends `bytes` as a string, replacing invalid UTF-8 sequences with
+    /// U+FFFD, and returns its [`StringId`].
+    ///
+    /// Replacement follows [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy),
+    /// but the converted text is written straight into the byte buffer. Each
+    /// replaced sequence becomes the 3-byte U+FFFD, so the stored string can
+    /// be longer than `bytes`; offset and byte limits apply to the converted
+    /// length.
+    ///
+    /// Returns the same errors as [`Self::try_push`], leaving the builder
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// let id = builder.try_push_lossy(b"caf\xE9!").unwrap();
+    /// assert_eq!(builder.build().get(id), Some("caf\u{FFFD}!"));
+    /// ```
+    pub fn try_push_lossy(&mut self, bytes: &[u8]) -> Result<StringId<I>> {
+        let id = self.next_id()?;
+        let start = self.bytes.len();
+        for chunk in bytes.utf8_chunks() {
+            self.bytes.extend_from_slice(chunk.valid().as_bytes());
+            if !chunk.invalid().is_empty() {
+                self.bytes.extend_from_slice(
+                    char::REPLACEMENT_CHARACTER
+                        .encode_utf8(&mut [0; 4])
+                        .as_bytes(),
+                );
+            }
+        }
+        self.try_commit(start, id)
+    }
+
+    /// Returns the raw ID the next pushed string will receive.
+    #[inline]
+    pub(crate) fn next_id(&self) -> Result<I> {
+        let id = self.len();
+        I::try_from_usize(id).ok_or(Error::TooManyStrings {
+            strings: id.saturating_add(1),
+            id_type: I::TYPE_NAME,
+        })
+    }
+
+    /// Records the bytes appended since `start` as a new string with ID `id`.
+    ///
+    /// For pushes that write into the byte buffer before the final length is
+    /// known. The appended bytes must be valid UTF-8. On failure, the buffer
+    /// is truncated back to `start`.
+    pub(crate) fn try_commit(&mut self, start: usize, id: I) -> Result<StringId<I>> {
+        if NULL_PADDED {
+            self.bytes.push(TERMINATOR);
+        }
+
+        let end = self.bytes.len();
+        match self.end_offset(end) {
+            Ok(end_offset) => {
+                self.offsets.push(end_offset);
+                Ok(StringId::new(id))
+            }
+            Err(error) => {
+                self.bytes.truncate(start);
+                Err(error)
+            }
+        }
+    }
+
+    /// Converts the byte length after a push into its end offset, enforcing
+    /// the byte limit and the offset type's range.
+    #[inline]
+    fn end_offset(&self, end: usize) -> Result<O> {
+        if end > self.byte_limit {
+            return Err(Error::ByteLimitExceeded {
+                bytes: end,
+                limit: self.byte_limit,
+            });
+        }
+        O::try_from_usize(end).ok_or(Error::TooManyBytesForOffsetType {
+            bytes: end,
+            offset_type: O::TYPE_NAME,
+        })
+    }
+
+    /// Finalizes into an immutable [`crate::StringTable`].
+    ///
+    /// This does not copy string bytes. Internal vectors are converted into
+    /// boxed slices so the resulting table is immutable and compact.
+    #[inline]
+    #[must_use]
+    pub fn build(self) -> StringTable<O, I, NULL_PADDED, A, TERMINATOR> {
+        let table = StringTable::from_parts_tagged(
+            self.bytes.into_boxed_slice(),
+            self.offsets.into_boxed_slice(),
+            self.tag,
+        );
+        debug_assert!(table.validate().is_ok());
+        table
+    }
+}
+
+#[inline]
+fn zero_offset<O: Offset>() -> O {
+    // SAFETY: All built-in integer implementations accept zero.
+    unsafe { O::try_from_usize(0).unwrap_unchecked() }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::PushOutcome;
+    use crate::allocator::Global;
+    use crate::{Error, StringId, StringTableBuilder};
+
+    #[test]
+    fn try_push_joined_writes_parts_and_separators() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let path = builder
+            .try_push_joined(["usr", "local", "bin"], "/")
+            .unwrap();
+        let single = builder.try_push_joined(["only"], ", ").unwrap();
+        let empty = builder
+            .try_push_joined(core::iter::empty::<&str>(), "/")
+            .unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get(path), Some("usr/local/bin"));
+        assert_eq!(table.get(single), Some("only"));
+        assert_eq!(table.get(empty), Some(""));
+        assert_eq!(table.as_bytes(), b"usr/local/bin\0only\0\0");
+    }
+
+    #[test]
+    fn try_push_joined_rolls_back_on_offset_overflow() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        builder.try_push("abc").unwrap();
+        let part = "x".repeat(100);
+
+        assert!(matches!(
+            builder.try_push_joined([&part, &part, &part], "-"),
+            Err(Error::TooManyBytesForOffsetType {
+                offset_type: "u8",
+                ..
+            })
+        ));
+        assert_eq!(builder.len(), 1);
+        assert_eq!(builder.bytes_len(), 3);
+    }
+
+    #[test]
+    fn try_push_lossy_replaces_invalid_sequences() {
+        let mut builder = StringTableBuilder::<u8, u8, true>::new_in(Global);
+        let clean = builder.try_push_lossy(b"ok").unwrap();
+        let dirty = builder.try_push_lossy(b"a\xF0\x9F\xFFb\xC3").unwrap();
+        assert!(matches!(
+            builder.try_push_lossy(&[0xFF; 100]),
+            Err(Error::TooManyBytesForOffsetType { .. })
+        ));
+
+        let table = builder.build();
+        assert_eq!(table.get(clean), Some("ok"));
+        assert_eq!(table.get(dirty), Some("a\u{FFFD}\u{FFFD}b\u{FFFD}"));
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn bytes_and_offsets_show_staged_data() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        assert_eq!(builder.bytes(), b"");
+        assert_eq!(builder.offsets(), [0]);
+
+        builder.try_push("ab").unwrap();
+        builder.try_push("").unwrap();
+        assert_eq!(builder.bytes(), b"ab\0\0");
+        assert_eq!(builder.offsets(), [0, 3, 4]);
+    }
+
+    #[test]
+    fn clear_keeps_capacity() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("hello").unwrap();
+        builder.try_push("world").unwrap();
+        let capacity = builder.bytes.capacity();
+
+        builder.clear();
+        assert!(builder.is_empty());
+        assert_eq!(builder.bytes_len(), 0);
+        assert_eq!(builder.bytes.capacity(), capacity);
+
+        let id = builder.try_push("again").unwrap();
+        assert_eq!(id, StringId::new(0));
+        assert_eq!(builder.build().offsets(), &[0u32, 5]);
+    }
+
+    #[test]
+    fn try_push_with_range_matches_byte_range() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let (a, a_range) = builder.try_push_with_range("abc").unwrap();
+        let (b, b_range) = builder.try_push_with_range("").unwrap();
+        let (c, c_range) = builder.try_push_with_range("de").unwrap();
+        assert_eq!(
+            (a_range.clone(), b_range.clone(), c_range.clone()),
+            (0..3, 4..4, 5..7)
+        );
+
+        let table = builder.build();
+        assert_eq!(table.byte_range(a), Some(a_range));
+        assert_eq!(table.byte_range(b), Some(b_range));
+        assert_eq!(&table.as_bytes()[c_range], b"de");
+        assert_eq!(table.get(c), Some("de"));
+    }
+
+    #[test]
+    fn try_push_or_skip_reports_too_many_strings() {
+        let mut builder = StringTableBuilder::<u32, u8>::new_in(Global);
+        for _ in 0..256 {
+            assert!(matches!(
+                builder.try_push_or_skip("x"),
+                PushOutcome::Pushed(_)
+            ));
+        }
+        assert_eq!(
+            builder.try_push_or_skip("x"),
+            PushOutcome::SkippedTooManyStrings
+        );
+        assert_eq!(builder.len(), 256);
+    }
+
+    #[test]
+    fn try_push_or_skip_reports_too_many_bytes_and_continues() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        assert_eq!(
+            builder.try_push_or_skip("abc"),
+            PushOutcome::Pushed(StringId::new(0))
+        );
+        assert_eq!(
+            builder.try_push_or_skip(&"x".repeat(300)),
+            PushOutcome::SkippedTooManyBytes
+        );
+        assert_eq!(
+            builder.try_push_or_skip("de"),
+            PushOutcome::Pushed(StringId::new(1))
+        );
+        assert!(builder.build().iter().eq(["abc", "de"]));
+    }
+
+    #[test]
+    fn byte_limit_rejects_pushes_past_limit() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.set_byte_limit(8);
+        builder.try_push("abc").unwrap();
+
+        assert_eq!(
+            builder.try_push("abcd"),
+            Err(Error::ByteLimitExceeded { bytes: 9, limit: 8 })
+        );
+        assert!(matches!(
+            builder.try_push_joined(["ab", "cd"], "/"),
+            Err(Error::ByteLimitExceeded { .. })
+        ));
+        assert_eq!(
+            builder.try_push_or_skip("abcd"),
+            PushOutcome::SkippedTooManyBytes
+        );
+        assert_eq!((builder.len(), builder.bytes_len()), (1, 4));
+
+        builder.try_push("abc").unwrap();
+        assert_eq!(builder.bytes_len(), 8);
+    }
+
+    #[test]
+    fn empty_table() {
+        let table = StringTableBuilder::new().build();
+        assert_eq!(table.len(), 0);
+        assert!(table.is_empty());
+        assert_eq!(table.as_bytes(), b"");
+        assert_eq!(table.offsets(), &[0u32]);
+    }
+
+    #[test]
+    fn single_string() {
+        let mut builder = StringTableBuilder::new();
+        let id = builder.try_push("hello").unwrap();
+        let table = builder.build();
+
+        assert_eq!(id, StringId::new(0));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(id), Some("hello"));
+        assert_eq!(table.offsets(), &[0u32, 5u32]);
+    }
+
+    #[test]
+    fn null_padded_single_string() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let id = builder.try_push("hello").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get(id), Some("hello"));
+        assert_eq!(table.as_bytes(), b"hello\0");
+        assert_eq!(table.offsets(), &[0u32, 6u32]);
+        assert_eq!(table.byte_range(id), Some(0..5));
+    }
+
+    #[test]
+    fn null_padded_custom_terminator() {
+        let mut builder = StringTableBuilder::<u32, u16, true, Global, b'\n'>::new_in(Global);
+        let a = builder.try_push("line").unwrap();
+        let b = builder.try_push("").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.as_bytes(), b"line\n\n");
+        assert_eq!(table.get(a), Some("line"));
+        assert_eq!(table.get(b), Some(""));
+        assert!(table.validate().is_ok());
+    }
+
+    #[test]
+    fn null_padded_empty_string() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let id = builder.try_push("").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get(id), Some(""));
+        assert_eq!(table.as_bytes(), b"\0");
+        assert_eq!(table.offsets(), &[0u32, 1u32]);
+        assert_eq!(table.byte_range(id), Some(0..0));
+    }
+
+    #[test]
+    fn multiple_with_empty_string() {
+        let mut builder = StringTableBuilder::new();
+        let a = builder.try_push("a").unwrap();
+        let b = builder.try_push("").unwrap();
+        let c = builder.try_push("ccc").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get(a), Some("a"));
+        assert_eq!(table.get(b), Some(""));
+        assert_eq!(table.get(c), Some("ccc"));
+        assert_eq!(table.offsets(), &[0u32, 1u32, 1u32, 4u32]);
+    }
+
+    #[test]
+    fn unicode_strings() {
+        let mut builder = StringTableBuilder::new();
+        let a = builder.try_push("猫").unwrap();
+        let b = builder.try_push("дом").unwrap();
+        let c = builder.try_push("music/曲").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get(a), Some("猫"));
+        assert_eq!(table.get(b), Some("дом"));
+        assert_eq!(table.get(c), Some("music/曲"));
+    }
+
+    #[test]
+    fn iter_matches_insert_order() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("z").unwrap();
+        builder.try_push("a").unwrap();
+        builder.try_push("m").unwrap();
+
+        let table = builder.build();
+        let got: alloc::vec::Vec<&str> = table.iter().collect();
+        assert_eq!(got, alloc::vec!["z", "a", "m"]);
+    }
+
+    #[test]
+    fn supports_custom_allocator() {
+        let mut builder = StringTableBuilder::<u32>::new_in(Global);
+        let id = builder.try_push("hello").unwrap();
+        let table = builder.build();
+        assert_eq!(table.get(id), Some("hello"));
+    }
+
+    #[test]
+    fn supports_small_offset_type() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        let id = builder.try_push("abc").unwrap();
+        let table = builder.build();
+        assert_eq!(table.get(id), Some("abc"));
+        assert_eq!(table.offsets(), &[0u8, 3u8]);
+    }
+
+    #[test]
+    fn supports_small_id_type() {
+        let mut builder = StringTableBuilder::<u32, u8>::new_in(Global);
+        let id = builder.try_push("abc").unwrap();
+        let table = builder.build();
+
+        assert_eq!(id, StringId::<u8>::new(0));
+        assert_eq!(table.get(id), Some("abc"));
+    }
+
+    #[test]
+    fn small_offset_type_reports_overflow() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        let long = "a".repeat(300);
+        let result = builder.try_push(&long);
+
+        assert!(matches!(
+            result,
+            Err(Error::TooManyBytesForOffsetType {
+                offset_type: "u8",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn small_id_type_reports_overflow() {
+        let mut builder = StringTableBuilder::<u32, u8>::new_in(Global);
+        for _ in 0..=u8::MAX {
+            builder.try_push("a").unwrap();
+        }
+
+        let result = builder.try_push("overflow");
+        assert!(matches!(
+            result,
+            Err(Error::TooManyStrings { id_type: "u8", .. })
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_vec_of_strings(values in proptest::collection::vec(".*", 0..256)) {
+            let mut builder = StringTableBuilder::new();
+            let mut ids = alloc::vec::Vec::with_capacity(values.len());
+
+            for value in &values {
+                ids.push(builder.try_push(value).unwrap());
+            }
+
+            let table = builder.build();
+
+            prop_assert_eq!(table.len(), values.len());
+            prop_assert_eq!(table.offsets().len(), values.len() + 1);
+
+            for (index, value) in values.iter().enumerate() {
+                let id = ids[index];
+                prop_assert_eq!(table.get(id), Some(value.as_str()));
+                prop_assert_eq!(
+                    table.byte_range(id).map(|r| &table.as_bytes()[r]),
+                    Some(value.as_bytes())
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Building from borrowed strings, copied once at the end.
+
+use core::marker::PhantomData;
+
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringId, StringIndex, StringTable};
+
+/// Builder that collects borrowed strings and copies them only in
+/// [`Self::build`].
+///
+/// Unlike [`crate::StringTableBuilder`], no byte buffer grows while pushing:
+/// only a vector of `&'a str` does. [`Self::build`] then allocates the byte
+/// buffer and offset table at their exact sizes and copies every string once.
+/// Use it when the source strings stay alive until the table is built.
+///
+/// Limits are still checked on each push, so [`Self::build`] cannot fail.
+/// Generic parameters match [`crate::StringTableBuilder`].
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::RefStringTableBuilder;
+///
+/// let source = String::from("alpha beta");
+/// let mut builder = RefStringTableBuilder::new();
+/// let ids: Vec<_> = source
+///     .split(' ')
+///     .map(|word| builder.try_push(word).unwrap())
+///     .collect();
+///
+/// let table = builder.build();
+/// drop(source);
+/// assert_eq!(table.get(ids[1]), Some("beta"));
+/// ```
+pub struct RefStringTableBuilder<
+    'a,
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    values: Vec<&'a str, A>,
+    bytes_len: usize,
+    _types: PhantomData<(O, I)>,
+}
+
+impl RefStringTableBuilder<'_, u32, u16, false, Global> {
+    /// Creates an empty builder using the global allocator.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl Default for RefStringTableBuilder<'_, u32, u16, false, Global> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        'a,
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > RefStringTableBuilder<'a, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates an empty builder with a custom allocator.
+    #[inline]
+    pub fn new_in(allocator: A) -> Self {
+        Self::with_capacity_in(0, allocator)
+    }
+
+    /// Creates a builder with room for `strings` borrowed strings and a
+    /// custom allocator.
+    pub fn with_capacity_in(strings: usize, allocator: A) -> Self {
+        Self {
+            values: Vec::with_capacity_in(strings, allocator),
+            bytes_len: 0,
+            _types: PhantomData,
+        }
+    }
+
+    /// Number of strings pushed so far.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` when no strings were pushed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Byte length the built table will have, including NUL terminators.
+    #[inline]
+    #[must_use]
+    pub fn bytes_len(&self) -> usize {
+        self.bytes_len
+    }
+
+    /// Records a string and returns its [`StringId`], without copying it.
+    ///
+    /// Returns the same errors as
+    /// [`StringTableBuilder::try_push`](crate::StringTableBuilder::try_push).
+    #[must_use = "the returned ID is needed to look the string up"]
+    pub fn try_push(&mut self, value: &'a str) -> Result<StringId<I>> {
+        let id = self.len();
+        let id = I::try_from_usize(id).ok_or(Error::TooManyStrings {
+            strings: id.saturating_add(1),
+            id_type: I::TYPE_NAME,
+        })?;
+
+        let added = value.len() + usize::from(NULL_PADDED);
+        let end = self
+            .bytes_len
+            .checked_add(added)
+            .filter(|&end| O::try_from_usize(end).is_some())
+            .ok_or(Error::TooManyBytesForOffsetType {
+                bytes: self.bytes_len.saturating_add(added),
+                offset_type: O::TYPE_NAME,
+            })?;
+
+        self.values.push(value);
+        self.bytes_len = end;
+        Ok(StringId::new(id))
+    }
+
+    /// Copies every pushed string into a new [`StringTable`].
+    ///
+    /// The byte buffer and offset table are each allocated once, at their
+    /// final size.
+    #[must_use]
+    pub fn build(self) -> StringTable<O, I, NULL_PADDED, A, TERMINATOR> {
+        let allocator = self.values.allocator().clone();
+        let mut bytes = Vec::with_capacity_in(self.bytes_len, allocator.clone());
+        let mut offsets = Vec::with_capacity_in(self.values.len() + 1, allocator);
+        // SAFETY: All built-in integer implementations accept zero.
+        offsets.push(unsafe { O::try_from_usize(0).unwrap_unchecked() });
+        for value in &self.values {
+            bytes.extend_from_slice(value.as_bytes());
+            if NULL_PADDED {
+                bytes.push(TERMINATOR);
+            }
+            // SAFETY: `try_push` checked that the running length, which
+            // never exceeds `bytes_len`, fits in `O`.
+            offsets.push(unsafe { O::try_from_usize(bytes.len()).unwrap_unchecked() });
+        }
+
+        let table =
+            StringTable::from_parts_unchecked(bytes.into_boxed_slice(), offsets.into_boxed_slice());
+        debug_assert!(table.validate().is_ok());
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{Error, RefStringTableBuilder};
+
+    #[test]
+    fn build_copies_borrowed_strings() {
+        let source = alloc::string::String::from("cat dog");
+        let mut builder = RefStringTableBuilder::<u8, u8, true>::new_in(Global);
+        for word in source.split(' ') {
+            builder.try_push(word).unwrap();
+        }
+        builder.try_push("").unwrap();
+        assert_eq!(builder.bytes_len(), 9);
+
+        let table = builder.build();
+        drop(source);
+        assert_eq!(table.as_bytes(), b"cat\0dog\0\0");
+        assert!(table.eq_strings(["cat", "dog", ""]));
+    }
+
+    #[test]
+    fn try_push_checks_limits() {
+        let long = "x".repeat(200);
+        let mut builder = RefStringTableBuilder::<u8, u8>::new_in(Global);
+        builder.try_push(&long).unwrap();
+        assert!(matches!(
+            builder.try_push(&long),
+            Err(Error::TooManyBytesForOffsetType { bytes: 400, .. })
+        ));
+        assert_eq!(builder.len(), 1);
+
+        let mut builder = RefStringTableBuilder::<u32, u8>::new_in(Global);
+        for _ in 0..256 {
+            builder.try_push("").unwrap();
+        }
+        assert!(matches!(
+            builder.try_push(""),
+            Err(Error::TooManyStrings { strings: 257, .. })
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+//! Unicode normalization on insert.
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::{Allocator, Offset, Result, StringId, StringIndex, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Appends `value` in Unicode Normalization Form C (NFC) and returns its
+    /// [`StringId`].
+    ///
+    /// When every string is pushed this way, byte equality of stored strings
+    /// matches Unicode canonical equivalence. Normalization can change the
+    /// byte length, so offset limits apply to the normalized form.
+    ///
+    /// Returns the same errors as [`Self::try_push`].
+    pub fn try_push_nfc(&mut self, value: &str) -> Result<StringId<I>> {
+        if is_nfc_quick(value.chars()) == IsNormalized::Yes {
+            return self.try_push(value);
+        }
+
+        let id = self.next_id()?;
+        let start = self.bytes.len();
+        let mut utf8 = [0u8; 4];
+        for c in value.nfc() {
+            self.bytes
+                .extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+        }
+        self.try_commit(start, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{Error, StringTableBuilder};
+
+    #[test]
+    fn try_push_nfc_stores_precomposed_form() {
+        let mut builder = StringTableBuilder::new();
+        let a = builder.try_push_nfc("cafe\u{301}").unwrap();
+        let b = builder.try_push_nfc("caf\u{e9}").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.as_bytes(), "caf\u{e9}caf\u{e9}".as_bytes());
+        assert_eq!(table.get(a), table.get(b));
+    }
+
+    #[test]
+    fn try_push_nfc_rolls_back_on_offset_overflow() {
+        let mut builder = StringTableBuilder::<u8>::new_in(Global);
+        builder.try_push("abc").unwrap();
+        let long = "e\u{301}".repeat(200);
+
+        assert!(matches!(
+            builder.try_push_nfc(&long),
+            Err(Error::TooManyBytesForOffsetType {
+                offset_type: "u8",
+                ..
+            })
+        ));
+        assert_eq!(builder.len(), 1);
+        assert_eq!(builder.bytes_len(), 3);
+    }
+}
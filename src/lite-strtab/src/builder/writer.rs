@@ -0,0 +1,246 @@
+//! Formatting strings straight into a builder.
+
+use core::fmt;
+
+use crate::allocator::*;
+use crate::{Offset, Result, StringId, StringIndex, StringTableBuilder};
+
+/// Writer returned by [`StringTableBuilder::string_writer`].
+///
+/// Implements [`fmt::Write`], appending formatted text directly to the
+/// builder's byte buffer, so `write!` needs no intermediate
+/// [`String`](alloc::string::String). Call [`Self::finish`] to record the
+/// text as a new string. Dropping the writer without finishing discards
+/// the text.
+///
+/// # Example
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use lite_strtab::StringTableBuilder;
+///
+/// let mut builder = StringTableBuilder::new();
+/// let mut writer = builder.string_writer();
+/// let (dir, index) = ("assets", 42);
+/// write!(writer, "{dir}/{index}").unwrap();
+/// let id = writer.finish().unwrap();
+///
+/// assert_eq!(builder.build().get(id), Some("assets/42"));
+/// ```
+#[must_use = "the written text is discarded unless `finish` is called"]
+pub struct BuilderStringWriter<
+    'b,
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    builder: &'b mut StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>,
+    start: usize,
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns a [`BuilderStringWriter`] that formats the next string
+    /// directly into this builder.
+    #[inline]
+    pub fn string_writer(&mut self) -> BuilderStringWriter<'_, O, I, NULL_PADDED, A, TERMINATOR> {
+        let start = self.bytes.len();
+        BuilderStringWriter {
+            builder: self,
+            start,
+        }
+    }
+
+    /// Appends `args` formatted as a single string and returns its
+    /// [`StringId`].
+    ///
+    /// Formats directly into the byte buffer, like [`Self::string_writer`],
+    /// so no intermediate [`String`](alloc::string::String) is allocated.
+    /// Returns the same errors as [`Self::try_push`]; on error, the builder
+    /// is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a formatting trait implementation returns an error, like
+    /// [`alloc::format!`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// let id = builder.try_push_fmt(format_args!("slot{}", 3)).unwrap();
+    /// assert_eq!(builder.build().get(id), Some("slot3"));
+    /// ```
+    pub fn try_push_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<StringId<I>> {
+        let mut writer = self.string_writer();
+        fmt::write(&mut writer, args).expect("a formatting trait implementation returned an error");
+        writer.finish()
+    }
+
+    /// Appends a single character as a string and returns its [`StringId`].
+    ///
+    /// The character is encoded on the stack. Returns the same errors as
+    /// [`Self::try_push`].
+    #[inline]
+    pub fn try_push_char(&mut self, c: char) -> Result<StringId<I>> {
+        self.try_push(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > BuilderStringWriter<'_, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Number of bytes written so far.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.builder.bytes.len() - self.start
+    }
+
+    /// Returns `true` when nothing has been written yet.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Records the written text as a new string and returns its
+    /// [`StringId`].
+    ///
+    /// Overflow is checked here rather than on every write. Returns the same
+    /// errors as [`StringTableBuilder::try_push`]; on error, the written text
+    /// is discarded and the builder is left unchanged.
+    pub fn finish(mut self) -> Result<StringId<I>> {
+        // On error, `drop` discards the written text.
+        let id = self.builder.next_id()?;
+        let result = self.builder.try_commit(self.start, id);
+        // Committed or already rolled back; nothing left for `drop` to undo.
+        self.start = self.builder.bytes.len();
+        result
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > fmt::Write for BuilderStringWriter<'_, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.builder.bytes.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > Drop for BuilderStringWriter<'_, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    fn drop(&mut self) {
+        // Bytes past the last offset would not belong to any string.
+        self.builder.bytes.truncate(self.start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::allocator::Global;
+    use crate::{Error, StringTableBuilder};
+
+    #[test]
+    fn string_writer_formats_into_builder() {
+        let mut builder = StringTableBuilder::<u32, u16, true>::new_in(Global);
+        builder.try_push("first").unwrap();
+        let mut writer = builder.string_writer();
+        let (name, index) = ("item", 7);
+        write!(writer, "{name}-{index:03}").unwrap();
+        assert_eq!(writer.len(), 8);
+        let id = writer.finish().unwrap();
+
+        // Dropped writers leave no trace.
+        let mut writer = builder.string_writer();
+        writer.write_str("discarded").unwrap();
+        drop(writer);
+
+        let table = builder.build();
+        assert_eq!(table.get(id), Some("item-007"));
+        assert_eq!(table.as_bytes(), b"first\0item-007\0");
+    }
+
+    #[test]
+    fn try_push_char_and_fmt_honor_padding() {
+        let mut builder = StringTableBuilder::<u32, u16, true>::new_in(Global);
+        let letter = builder.try_push_char('\u{e9}').unwrap();
+        let formatted = builder.try_push_fmt(format_args!("{}+{}", 1, 2)).unwrap();
+
+        let table = builder.build();
+        assert_eq!(table.get(letter), Some("\u{e9}"));
+        assert_eq!(table.get(formatted), Some("1+2"));
+        assert_eq!(table.as_bytes(), b"\xC3\xA9\x001+2\x00");
+    }
+
+    #[test]
+    fn string_writer_checks_overflow_at_finish() {
+        let mut builder = StringTableBuilder::<u8, u8>::new_in(Global);
+        builder.try_push("kept").unwrap();
+        let mut writer = builder.string_writer();
+        for _ in 0..64 {
+            writer.write_str("long").unwrap();
+        }
+        assert!(matches!(
+            writer.finish(),
+            Err(Error::TooManyBytesForOffsetType { .. })
+        ));
+
+        assert_eq!(builder.bytes(), b"kept");
+        let padding = "x".repeat(255);
+        assert!(builder.try_push_fmt(format_args!("{padding}")).is_err());
+        assert_eq!(builder.bytes(), b"kept");
+        assert!(builder.build().eq_strings(["kept"]));
+    }
+
+    #[test]
+    fn string_writer_discards_text_on_too_many_strings() {
+        let mut builder = StringTableBuilder::<u32, u8>::new_in(Global);
+        for _ in 0..256 {
+            builder.try_push("").unwrap();
+        }
+        let mut writer = builder.string_writer();
+        writer.write_str("abc").unwrap();
+        assert!(matches!(
+            writer.finish(),
+            Err(Error::TooManyStrings { strings: 257, .. })
+        ));
+        assert!(builder.try_push_fmt(format_args!("{}", 1)).is_err());
+
+        assert_eq!(builder.bytes(), b"");
+        assert_eq!(builder.build().validate(), Ok(()));
+    }
+}
@@ -1,4 +1,4 @@
-//! Error types for string pool construction and internal validation.
+//! Error types for string pool construction and table validation.
 
 /// Result type used by this crate.
 pub type Result<T> = core::result::Result<T, Error>;
@@ -28,48 +28,209 @@ pub enum Error {
         /// Offset type used by the pool/builder.
         offset_type: &'static str,
     },
+    /// The total byte length exceeded the limit set with
+    /// [`crate::StringTableBuilder::set_byte_limit`], or the buffer given to a
+    /// [`crate::BufferStringTableBuilder`].
+    #[error("cannot store {bytes} bytes of string data: byte limit is {limit}")]
+    ByteLimitExceeded {
+        /// Attempted byte length.
+        bytes: usize,
+        /// Configured byte limit.
+        limit: usize,
+    },
+    /// A string ID was past the end of the table it was used with.
+    #[error("string id {id} is out of bounds for a table of {len} strings")]
+    IdOutOfBounds {
+        /// Index of the rejected ID.
+        id: usize,
+        /// Number of strings in the table.
+        len: usize,
+    },
+    /// Encoded input could not be decoded into a table.
+    #[error("invalid encoded string table at byte {position}: {reason}")]
+    InvalidEncoding {
+        /// Byte position in the input where decoding failed.
+        position: usize,
+        /// What was wrong with the input.
+        reason: &'static str,
+    },
+    /// Decoded or externally supplied parts do not form a valid table.
+    #[error(transparent)]
+    InvalidTable(#[from] ValidationError),
 }
 
-#[cfg(any(debug_assertions, test))]
 pub(crate) type ValidationResult<T> = core::result::Result<T, ValidationError>;
 
-#[cfg(any(debug_assertions, test))]
+/// Ways in which a byte buffer and offset table can fail to form a valid
+/// [`crate::StringTable`].
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
-pub(crate) enum ValidationError {
+pub enum ValidationError {
+    /// The string count does not fit in the ID type.
     #[error("invalid string table: {strings} strings do not fit in id type '{id_type}'")]
     TooManyStrings {
+        /// Number of strings in the table.
         strings: usize,
+        /// ID type of the table.
         id_type: &'static str,
     },
+    /// The byte length does not fit in the offset type.
     #[error("invalid string table: {bytes} bytes do not fit in offset type '{offset_type}'")]
     TooManyBytesForOffsetType {
+        /// Length of the byte buffer.
         bytes: usize,
+        /// Offset type of the table.
         offset_type: &'static str,
     },
-    #[error("invalid string table: offsets must end with a sentinel equal to total byte length")]
+    /// The offset table is empty.
+    #[error(
+        "invalid string table: offsets must end with a sentinel marking the end of the string data"
+    )]
     MissingSentinelOffset,
-    #[error("invalid string table: final offset is {found}, but byte length is {expected}")]
-    LastOffsetMismatch { found: usize, expected: usize },
+    /// The sentinel offset points past the end of the byte buffer.
+    #[error("invalid string table: final offset is {found}, past the byte length {expected}")]
+    LastOffsetMismatch {
+        /// Value of the sentinel offset.
+        found: usize,
+        /// Length of the byte buffer.
+        expected: usize,
+    },
+    /// A byte past the sentinel is not NUL.
+    ///
+    /// Bytes after the sentinel are only allowed as NUL alignment padding,
+    /// as added by [`crate::StringTableBuilder::build_aligned`].
+    #[error("invalid string table: padding byte at {position} past the final offset is not NUL")]
+    NonNulPadding {
+        /// Position of the first non-NUL byte in the byte buffer.
+        position: usize,
+    },
+    /// An offset points past the end of the byte buffer.
     #[error("invalid string table: offset[{index}] = {offset} is out of bounds (byte length {bytes_len})")]
     OffsetOutOfBounds {
+        /// Position in the offset table.
         index: usize,
+        /// Value of the offset.
         offset: usize,
+        /// Length of the byte buffer.
         bytes_len: usize,
     },
+    /// An offset is smaller than the one before it.
     #[error(
         "invalid string table: offsets must be non-decreasing; offset[{index}] = {current}, previous = {previous}"
     )]
     OffsetsNotMonotonic {
+        /// Position in the offset table.
         index: usize,
+        /// Value of the preceding offset.
         previous: usize,
+        /// Value of the offset at `index`.
         current: usize,
     },
+    /// A string's bytes are not valid UTF-8.
     #[error("invalid string table: bytes for string index {index} are not valid UTF-8")]
-    InvalidUtf8 { index: usize },
+    InvalidUtf8 {
+        /// Index of the string.
+        index: usize,
+    },
+    /// A string in a null-padded table has no room for its terminator.
     #[error("invalid string table: string index {index} in null-padded mode has no trailing byte")]
-    NullPaddedStringMissingTerminatorByte { index: usize },
+    NullPaddedStringMissingTerminatorByte {
+        /// Index of the string.
+        index: usize,
+    },
+    /// A string in a null-padded table does not end with the table's
+    /// terminator byte (NUL by default).
     #[error(
-        "invalid string table: string index {index} in null-padded mode must end with a NUL byte"
+        "invalid string table: string index {index} in null-padded mode must end with its terminator byte"
     )]
-    NullPaddedStringMissingTrailingNul { index: usize },
+    NullPaddedStringMissingTrailingNul {
+        /// Index of the string.
+        index: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::{self, Write};
+
+    use super::{Error, ValidationError};
+
+    /// Fixed-capacity `core::fmt::Write` sink, so formatting needs neither
+    /// `std` nor `alloc`.
+    struct FixedBuf {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl FixedBuf {
+        fn new() -> Self {
+            Self {
+                bytes: [0; 128],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for FixedBuf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display_formats_through_core_fmt() {
+        let cases = [
+            (
+                Error::TooManyStrings {
+                    strings: 65537,
+                    id_type: "u16",
+                },
+                "cannot store 65537 strings: id type 'u16' is too small; use a larger StringId type",
+            ),
+            (
+                Error::TooManyBytesForOffsetType {
+                    bytes: 256,
+                    offset_type: "u8",
+                },
+                "cannot store 256 bytes of string data: offset type 'u8' is too small; use a larger offset type",
+            ),
+            (
+                Error::ByteLimitExceeded {
+                    bytes: 70000,
+                    limit: 65536,
+                },
+                "cannot store 70000 bytes of string data: byte limit is 65536",
+            ),
+            (
+                Error::IdOutOfBounds { id: 5, len: 3 },
+                "string id 5 is out of bounds for a table of 3 strings",
+            ),
+            (
+                Error::InvalidEncoding {
+                    position: 7,
+                    reason: "truncated varint",
+                },
+                "invalid encoded string table at byte 7: truncated varint",
+            ),
+            (
+                Error::InvalidTable(ValidationError::InvalidUtf8 { index: 3 }),
+                "invalid string table: bytes for string index 3 are not valid UTF-8",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let mut buf = FixedBuf::new();
+            write!(buf, "{error}").unwrap();
+            assert_eq!(buf.as_str(), expected);
+        }
+    }
 }
@@ -0,0 +1,310 @@
+//! Deduplicating builder for creating an immutable [`crate::StringTable`].
+//!
+//! [`StringInterner`] wraps a [`crate::StringTableBuilder`] with a hash index
+//! over the strings pushed so far. The index stores only [`StringId`]s and
+//! hashes the builder's own bytes, so no string is stored twice.
+//!
+//! The index hashes with [`hashbrown::DefaultHashBuilder`] (foldhash, a fast
+//! non-cryptographic hasher) unless another [`core::hash::BuildHasher`] is
+//! supplied via [`StringInterner::with_hasher_in`].
+
+use core::hash::BuildHasher;
+
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+use crate::allocator::*;
+use crate::{Offset, Result, StringId, StringIndex, StringTable, StringTableBuilder};
+
+/// Builder for [`crate::StringTable`] that returns the existing [`StringId`]
+/// when a string is pushed again.
+///
+/// Generic parameters match [`crate::StringTableBuilder`], plus the hasher
+/// `S` used by the index.
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::StringInterner;
+///
+/// let mut interner = StringInterner::new();
+/// let a = interner.try_push_interned("cat").unwrap();
+/// let b = interner.try_push_interned("dog").unwrap();
+/// assert_eq!(interner.try_push_interned("cat").unwrap(), a);
+///
+/// let table = interner.build();
+/// assert_eq!(table.len(), 2);
+/// assert_eq!(table.get(b), Some("dog"));
+/// ```
+pub struct StringInterner<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+    S = DefaultHashBuilder,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    builder: StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>,
+    index: HashTable<StringId<I>>,
+    hasher: S,
+}
+
+impl StringInterner<u32, u16, false, Global> {
+    /// Creates an empty interner using the global allocator.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates an interner with reserved capacities using the global allocator.
+    ///
+    /// `strings` is the expected number of distinct strings, `bytes` is the
+    /// expected total number of UTF-8 bytes across them.
+    #[inline]
+    pub fn with_capacity(strings: usize, bytes: usize) -> Self {
+        Self::with_capacity_in(strings, bytes, Global)
+    }
+}
+
+impl StringTableBuilder<u32, u16, false, Global> {
+    /// Creates an empty [`StringInterner`] whose index hashes with `hasher`,
+    /// using the global allocator.
+    ///
+    /// See [`StringInterner::with_hasher_in`].
+    #[inline]
+    pub fn with_interner<S: BuildHasher>(
+        hasher: S,
+    ) -> StringInterner<u32, u16, false, Global, 0, S> {
+        StringInterner::with_hasher_in(hasher, Global)
+    }
+}
+
+impl Default for StringInterner<u32, u16, false, Global> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringInterner<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates an empty interner with a custom allocator.
+    #[inline]
+    pub fn new_in(allocator: A) -> Self {
+        Self::with_hasher_in(DefaultHashBuilder::default(), allocator)
+    }
+
+    /// Creates an interner with reserved capacities and a custom allocator.
+    ///
+    /// Both the builder and the hash index are pre-sized. The index accounts
+    /// for its load factor, so interning up to `strings` distinct strings
+    /// never rehashes.
+    #[inline]
+    pub fn with_capacity_in(strings: usize, bytes: usize, allocator: A) -> Self {
+        Self::with_capacity_and_hasher_in(strings, bytes, DefaultHashBuilder::default(), allocator)
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+        S: BuildHasher,
+    > StringInterner<O, I, NULL_PADDED, A, TERMINATOR, S>
+{
+    /// Creates an empty interner whose index hashes with `hasher`, with a
+    /// custom allocator.
+    ///
+    /// Strings are hashed once per push, so a hasher tuned for short keys,
+    /// such as `ahash`, can speed up interning many mostly-duplicate strings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use core::hash::BuildHasherDefault;
+    /// use std::collections::hash_map::DefaultHasher;
+    ///
+    /// use lite_strtab::{Global, StringInterner};
+    ///
+    /// let mut interner = StringInterner::<u32, u16, false, Global, 0, _>::with_hasher_in(
+    ///     BuildHasherDefault::<DefaultHasher>::default(),
+    ///     Global,
+    /// );
+    /// let id = interner.try_push_interned("cat").unwrap();
+    /// assert_eq!(interner.try_push_interned("cat").unwrap(), id);
+    /// ```
+    pub fn with_hasher_in(hasher: S, allocator: A) -> Self {
+        Self {
+            builder: StringTableBuilder::new_in(allocator),
+            index: HashTable::new(),
+            hasher,
+        }
+    }
+
+    /// Creates an interner with reserved capacities, whose index hashes with
+    /// `hasher`, with a custom allocator.
+    ///
+    /// See [`Self::with_capacity_in`] and [`Self::with_hasher_in`].
+    pub fn with_capacity_and_hasher_in(
+        strings: usize,
+        bytes: usize,
+        hasher: S,
+        allocator: A,
+    ) -> Self {
+        Self {
+            builder: StringTableBuilder::with_capacity_in(strings, bytes, allocator),
+            index: HashTable::with_capacity(strings),
+            hasher,
+        }
+    }
+
+    /// Number of distinct strings interned.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    /// Returns `true` when no strings were interned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    /// Current total byte length of interned string data.
+    #[inline]
+    pub fn bytes_len(&self) -> usize {
+        self.builder.bytes_len()
+    }
+
+    /// Returns the [`StringId`] of `value`, appending it first if it was not
+    /// interned yet.
+    ///
+    /// Returns the same errors as [`crate::StringTableBuilder::try_push`].
+    #[inline]
+    pub fn try_push_interned(&mut self, value: &str) -> Result<StringId<I>> {
+        self.try_push_interned_tracked(value).map(|(id, _)| id)
+    }
+
+    /// Like [`Self::try_push_interned`], but also returns `true` when `value`
+    /// was newly inserted and `false` when an existing ID was returned.
+    ///
+    /// Useful for counting deduplication savings, mapping external indices, or
+    /// running one-time setup for each new string.
+    #[doc(alias = "intern_tracked")]
+    pub fn try_push_interned_tracked(&mut self, value: &str) -> Result<(StringId<I>, bool)> {
+        let hash = self.hasher.hash_one(value);
+        let builder = &self.builder;
+        if let Some(&id) = self
+            .index
+            .find(hash, |&id| builder.str_at(id.into_usize()) == value)
+        {
+            return Ok((id, false));
+        }
+
+        let id = self.builder.try_push(value)?;
+        let Self {
+            builder,
+            index,
+            hasher,
+        } = self;
+        index.insert_unique(hash, id, |&id| {
+            hasher.hash_one(builder.str_at(id.into_usize()))
+        });
+        Ok((id, true))
+    }
+
+    /// Finalizes into an immutable [`crate::StringTable`], dropping the index.
+    #[inline]
+    pub fn build(self) -> StringTable<O, I, NULL_PADDED, A, TERMINATOR> {
+        self.builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::hash::BuildHasherDefault;
+
+    use crate::{StringInterner, StringTableBuilder};
+
+    #[test]
+    fn custom_hasher_interns_duplicates_once() {
+        let mut interner =
+            StringTableBuilder::with_interner(BuildHasherDefault::<ahash::AHasher>::default());
+        let ids = ["x", "y", "x"].map(|value| interner.try_push_interned(value).unwrap());
+
+        assert_eq!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[1]);
+        assert!(interner.build().eq_strings(["x", "y"]));
+    }
+
+    #[test]
+    fn interns_duplicates_once() {
+        let values = ["a", "b", "a", "c", "b", "a"];
+        let mut interner = StringInterner::with_capacity(3, 3);
+        let ids: alloc::vec::Vec<_> = values
+            .iter()
+            .map(|value| interner.try_push_interned(value).unwrap())
+            .collect();
+
+        assert_eq!(interner.len(), 3);
+        assert_eq!(ids[0], ids[2]);
+        assert_eq!(ids[0], ids[5]);
+        assert_eq!(ids[1], ids[4]);
+
+        let table = interner.build();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.as_bytes(), b"abc");
+        for (id, value) in ids.iter().zip(values) {
+            assert_eq!(table.get(*id), Some(value));
+        }
+    }
+
+    #[test]
+    fn tracked_push_reports_new_and_existing() {
+        let mut interner = StringInterner::new();
+        let (a, inserted) = interner.try_push_interned_tracked("cat").unwrap();
+        assert!(inserted);
+
+        let (b, inserted) = interner.try_push_interned_tracked("dog").unwrap();
+        assert!(inserted);
+
+        assert_eq!(
+            interner.try_push_interned_tracked("cat").unwrap(),
+            (a, false)
+        );
+        assert_eq!(interner.try_push_interned("dog").unwrap(), b);
+        assert_eq!(
+            interner.try_push_interned_tracked("dog").unwrap(),
+            (b, false)
+        );
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn with_capacity_does_not_grow_for_known_corpus() {
+        let mut interner = StringInterner::with_capacity(64, 64 * 4);
+        let index_capacity = interner.index.capacity();
+        for round in 0..2 {
+            for value in 0..64 {
+                let id = interner
+                    .try_push_interned(&alloc::format!("{value:04}"))
+                    .unwrap();
+                assert_eq!(id.into_usize(), value, "round {round}");
+            }
+        }
+
+        assert_eq!(interner.len(), 64);
+        assert_eq!(interner.bytes_len(), 64 * 4);
+        assert_eq!(interner.index.capacity(), index_capacity);
+    }
+}
@@ -1,6 +1,6 @@
 #![doc = include_str!(concat!("../", env!("CARGO_PKG_README")))]
 #![no_std]
-#![cfg_attr(feature = "nightly", feature(allocator_api))]
+#![cfg_attr(feature = "nightly", feature(allocator_api, try_trait_v2))]
 #![forbid(unsafe_op_in_unsafe_fn)]
 #![warn(missing_docs)]
 
@@ -8,19 +8,65 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod aligned;
 mod allocator;
 
 /// Builder for creating immutable pools.
 pub mod builder;
 /// Error types.
 pub mod error;
+/// Deduplicating builder.
+pub mod interner;
 /// Immutable pool storage and iteration.
 pub mod table;
 /// Data and type definitions.
 pub mod types;
 
-pub use allocator::{Allocator, Global};
-pub use builder::{StringPoolBuilder, StringTableBuilder};
-pub use error::{Error, Result};
-pub use table::{StringPool, StringPoolIter, StringTable, StringTableIter};
-pub use types::{Offset, StringId, StringIndex};
+pub use aligned::AlignedAllocator;
+pub use allocator::{Allocator, Global, TryReserveError};
+pub use builder::{
+    BufferStringTableBuilder, BuilderStringWriter, Checkpoint, PushOutcome, RefStringTableBuilder,
+    StringPoolBuilder, StringTableBuilder,
+};
+pub use error::{Error, Result, ValidationError};
+pub use interner::StringInterner;
+#[cfg(feature = "rkyv")]
+pub use table::{ArchivedStringTable, StringTableResolver};
+pub use table::{
+    BorrowedStringTable, CompactTable, Entry, PackedStringTable, StringPool, StringPoolIter,
+    StringTable, StringTableBytesIter, StringTableIter, TableDiff, TableHeader,
+};
+pub use types::{BrandedStringId, Offset, StringId, StringIndex};
+
+/// Compile-fail checks that ignored results are reported.
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// let mut builder = lite_strtab::StringTableBuilder::new();
+/// builder.try_push("dropped");
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// let mut builder = lite_strtab::StringTableBuilder::new();
+/// builder.try_push_or_skip("dropped");
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// lite_strtab::StringTableBuilder::new().build();
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// let table = lite_strtab::StringTable::empty();
+/// table.get_by_index(0);
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// let table = lite_strtab::StringTable::empty();
+/// table.iter();
+/// ```
+#[cfg(doctest)]
+pub struct MustUseTests;
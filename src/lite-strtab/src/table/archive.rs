@@ -0,0 +1,282 @@
+//! `rkyv` zero-copy archival.
+//!
+//! A [`StringTable`] archives as an [`ArchivedStringTable`]: the byte buffer
+//! and offset table become two rkyv vectors, so strings can be read straight
+//! from the archive without deserializing. Checked access through
+//! [`rkyv::access`] validates the table invariants along with the archive
+//! layout, and deserializing validates the rebuilt table again.
+
+use core::marker::PhantomData;
+use core::ptr::addr_of;
+use core::str;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::munge::munge;
+use rkyv::rancor::{Fallible, Infallible, ResultExt, Source, Strategy};
+use rkyv::ser::{Allocator as ArchiveAllocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Archived, Deserialize, Place, Portable, Serialize};
+
+use super::validate::check_parts;
+use crate::allocator::{Global, Vec};
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+/// Archived form of a [`StringTable`] using the global allocator.
+///
+/// Supports lookups and iteration directly on archived bytes. Offsets are
+/// stored as rkyv's archived form of `O`, so the archive is as compact as the
+/// table it was created from.
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::{ArchivedStringTable, StringTable, StringTableBuilder};
+/// use rkyv::rancor::Error;
+///
+/// let mut builder = StringTableBuilder::new();
+/// let hello = builder.try_push("hello").unwrap();
+/// builder.try_push("world").unwrap();
+/// let table = builder.build();
+///
+/// let archive = rkyv::to_bytes::<Error>(&table).unwrap();
+/// let archived = rkyv::access::<ArchivedStringTable, Error>(&archive).unwrap();
+/// assert_eq!(archived.get(hello), Some("hello"));
+/// assert!(archived.iter().eq(["hello", "world"]));
+///
+/// let restored: StringTable = rkyv::deserialize::<_, Error>(archived).unwrap();
+/// assert!(restored == table);
+/// ```
+#[repr(C)]
+pub struct ArchivedStringTable<
+    O: Archive = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    const TERMINATOR: u8 = 0,
+> {
+    bytes: ArchivedVec<u8>,
+    offsets: ArchivedVec<Archived<O>>,
+    _id: PhantomData<I>,
+}
+
+// SAFETY: The struct is `repr(C)` and every field is `Portable`.
+unsafe impl<O: Archive, I, const NULL_PADDED: bool, const TERMINATOR: u8> Portable
+    for ArchivedStringTable<O, I, NULL_PADDED, TERMINATOR>
+{
+}
+
+/// Resolver for [`ArchivedStringTable`].
+pub struct StringTableResolver {
+    bytes: VecResolver,
+    offsets: VecResolver,
+}
+
+impl<O, I, const NULL_PADDED: bool, const TERMINATOR: u8>
+    ArchivedStringTable<O, I, NULL_PADDED, TERMINATOR>
+where
+    O: Offset + Archive,
+    I: StringIndex,
+    Archived<O>: Deserialize<O, Strategy<(), Infallible>>,
+{
+    /// Number of strings in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` when the table contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the contiguous byte storage.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Returns the string for `id`, or [`None`] when it is out of bounds.
+    #[inline]
+    pub fn get(&self, id: StringId<I>) -> Option<&str> {
+        self.get_by_index(id.into_usize())
+    }
+
+    /// Returns the string at a raw `usize` index.
+    ///
+    /// See [`StringTable::get_by_index`].
+    #[inline]
+    pub fn get_by_index(&self, index: usize) -> Option<&str> {
+        (index < self.len()).then(|| self.str_at(index))
+    }
+
+    /// Iterates over all strings in ID order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator + '_ {
+        (0..self.len()).map(|index| self.str_at(index))
+    }
+
+    /// Reads offset `index` as a [`usize`].
+    #[inline]
+    fn offset_at(&self, index: usize) -> usize {
+        let offset: O = self.offsets[index]
+            .deserialize(Strategy::wrap(&mut ()))
+            .always_ok();
+        offset.to_usize()
+    }
+
+    /// Returns string `index`, which must be below [`Self::len`].
+    #[inline]
+    fn str_at(&self, index: usize) -> &str {
+        let start = self.offset_at(index);
+        let end = self.offset_at(index + 1) - usize::from(NULL_PADDED);
+        let bytes = &self.bytes[start..end];
+        // SAFETY: Archived tables uphold the table invariants, either checked
+        // by `CheckBytes` or guaranteed by the caller of unchecked access.
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl<O, I, const NULL_PADDED: bool, const TERMINATOR: u8> Archive
+    for StringTable<O, I, NULL_PADDED, Global, TERMINATOR>
+where
+    O: Offset + Archive,
+    I: StringIndex,
+{
+    type Archived = ArchivedStringTable<O, I, NULL_PADDED, TERMINATOR>;
+    type Resolver = StringTableResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedStringTable { bytes, offsets, _id: _ } = out);
+        ArchivedVec::resolve_from_slice(&self.bytes, resolver.bytes, bytes);
+        ArchivedVec::resolve_from_slice(&self.offsets, resolver.offsets, offsets);
+    }
+}
+
+impl<S, O, I, const NULL_PADDED: bool, const TERMINATOR: u8> Serialize<S>
+    for StringTable<O, I, NULL_PADDED, Global, TERMINATOR>
+where
+    S: Fallible + ArchiveAllocator + Writer + ?Sized,
+    O: Offset + Serialize<S>,
+    I: StringIndex,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(StringTableResolver {
+            bytes: ArchivedVec::serialize_from_slice(&self.bytes, serializer)?,
+            offsets: ArchivedVec::serialize_from_slice(&self.offsets, serializer)?,
+        })
+    }
+}
+
+impl<D, O, I, const NULL_PADDED: bool, const TERMINATOR: u8>
+    Deserialize<StringTable<O, I, NULL_PADDED, Global, TERMINATOR>, D>
+    for ArchivedStringTable<O, I, NULL_PADDED, TERMINATOR>
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+    O: Offset + Archive,
+    I: StringIndex,
+    Archived<O>: Deserialize<O, D>,
+{
+    /// Copies the archive into an owned table.
+    ///
+    /// Fails with the rebuilt table's [`crate::ValidationError`] if it does
+    /// not uphold the table invariants.
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<StringTable<O, I, NULL_PADDED, Global, TERMINATOR>, D::Error> {
+        let mut bytes = Vec::with_capacity_in(self.bytes.len(), Global);
+        bytes.extend_from_slice(&self.bytes);
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), Global);
+        for offset in self.offsets.iter() {
+            offsets.push(offset.deserialize(deserializer)?);
+        }
+
+        let table =
+            StringTable::from_parts_unchecked(bytes.into_boxed_slice(), offsets.into_boxed_slice());
+        table.validate().map_err(D::Error::new)?;
+        Ok(table)
+    }
+}
+
+// SAFETY: `check_bytes` checks both vectors, then every table invariant
+// relied on by the accessors.
+unsafe impl<C, O, I, const NULL_PADDED: bool, const TERMINATOR: u8> CheckBytes<C>
+    for ArchivedStringTable<O, I, NULL_PADDED, TERMINATOR>
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+    O: Offset + Archive,
+    I: StringIndex,
+    Archived<O>: Deserialize<O, Strategy<(), Infallible>>,
+    ArchivedVec<u8>: CheckBytes<C>,
+    ArchivedVec<Archived<O>>: CheckBytes<C>,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        // SAFETY: The caller guarantees `value` is aligned and points to
+        // enough bytes for `Self`, so it does for each field too.
+        unsafe {
+            ArchivedVec::<u8>::check_bytes(addr_of!((*value).bytes), context)?;
+            ArchivedVec::<Archived<O>>::check_bytes(addr_of!((*value).offsets), context)?;
+        }
+
+        // SAFETY: Both fields were just checked and `_id` is zero-sized.
+        let table = unsafe { &*value };
+        check_parts::<O, I, NULL_PADDED, TERMINATOR>(
+            table.as_bytes(),
+            table.offsets.len(),
+            |index| table.offset_at(index),
+        )
+        .map_err(C::Error::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use super::ArchivedStringTable;
+    use crate::allocator::Global;
+    use crate::{StringTable, StringTableBuilder};
+
+    #[test]
+    fn archived_table_reads_without_deserializing() {
+        let mut builder = StringTableBuilder::<u16, u8, true>::new_in(Global);
+        for value in ["alpha", "", "caf\u{e9}"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let archive = rkyv::to_bytes::<Error>(&table).unwrap();
+        let archived = rkyv::access::<ArchivedStringTable<u16, u8, true>, Error>(&archive).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived.as_bytes(), table.as_bytes());
+        assert_eq!(archived.get_by_index(2), Some("caf\u{e9}"));
+        assert_eq!(archived.get_by_index(3), None);
+        assert!(archived.iter().rev().eq(["caf\u{e9}", "", "alpha"]));
+
+        let restored: StringTable<u16, u8, true> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert!(restored == table);
+    }
+
+    #[test]
+    fn invalid_archive_is_rejected() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("ok").unwrap();
+        builder.try_push("zq").unwrap();
+        let table = builder.build();
+
+        let mut archive = rkyv::to_bytes::<Error>(&table).unwrap();
+        let position = archive
+            .windows(4)
+            .position(|window| window == b"okzq")
+            .unwrap();
+        archive[position + 2] = 0xFF;
+        assert!(rkyv::access::<ArchivedStringTable, Error>(&archive).is_err());
+
+        // SAFETY: Only a string byte was changed, so the vectors are still
+        // well-formed; deserializing reads them without relying on the table
+        // invariants.
+        let archived = unsafe { rkyv::access_unchecked::<ArchivedStringTable>(&archive) };
+        assert!(rkyv::deserialize::<StringTable, Error>(archived).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+//! Apache Arrow interop.
+//!
+//! Arrow's [`StringArray`] uses the same layout as [`StringTable`]: one
+//! values buffer plus `len + 1` offsets. Conversion copies the values in one
+//! block and narrows offsets to the [`i32`] type Arrow mandates.
+
+use alloc::vec::Vec;
+
+use arrow_array::StringArray;
+use arrow_buffer::{Buffer, OffsetBuffer, ScalarBuffer};
+
+use crate::{Allocator, Error, Offset, Result, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Converts the table into an Arrow [`StringArray`] with no null entries.
+    ///
+    /// The byte buffer is copied once and offsets are converted to [`i32`].
+    /// In null-padded mode, terminators are dropped since Arrow strings are
+    /// delimited by offsets alone.
+    ///
+    /// Returns [`Error::TooManyBytesForOffsetType`] when the string data
+    /// exceeds [`i32::MAX`] bytes, the most a [`StringArray`] can address.
+    pub fn to_arrow_string_array(&self) -> Result<StringArray> {
+        // Null-padded strings lose one terminator byte each.
+        let values_len =
+            self.offsets[self.len()].to_usize() - self.len() * usize::from(NULL_PADDED);
+        if i32::try_from(values_len).is_err() {
+            return Err(Error::TooManyBytesForOffsetType {
+                bytes: values_len,
+                offset_type: "i32",
+            });
+        }
+
+        let mut offsets = Vec::with_capacity(self.offsets.len());
+        let values = if NULL_PADDED {
+            let mut values = Vec::with_capacity(values_len);
+            offsets.push(0i32);
+            for value in self.iter() {
+                values.extend_from_slice(value.as_bytes());
+                // Cannot truncate: `values.len() <= values_len <= i32::MAX`.
+                offsets.push(values.len() as i32);
+            }
+            Buffer::from_vec(values)
+        } else {
+            // Cannot truncate: every offset is at most `values_len <= i32::MAX`.
+            offsets.extend(self.offsets.iter().map(|offset| offset.to_usize() as i32));
+            Buffer::from_slice_ref(&self.bytes[..values_len])
+        };
+
+        // SAFETY: Offsets start at zero, are non-decreasing, end at
+        // `values.len()`, and every range is valid UTF-8 by table invariants.
+        let offsets = unsafe { OffsetBuffer::new_unchecked(ScalarBuffer::from(offsets)) };
+        Ok(unsafe { StringArray::new_unchecked(offsets, values, None) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::Array;
+
+    use crate::StringTableBuilder;
+
+    #[test]
+    fn converts_to_string_array() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("cat").unwrap();
+        builder.try_push("").unwrap();
+        builder.try_push("猫").unwrap();
+        let table = builder.build();
+
+        let array = table.to_arrow_string_array().unwrap();
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.null_count(), 0);
+        assert_eq!(array.value(0), "cat");
+        assert_eq!(array.value(1), "");
+        assert_eq!(array.value(2), "猫");
+    }
+
+    #[test]
+    fn null_padded_conversion_drops_terminators() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("cat").unwrap();
+        builder.try_push("dog").unwrap();
+        let table = builder.build();
+
+        let array = table.to_arrow_string_array().unwrap();
+        assert_eq!(array.value(0), "cat");
+        assert_eq!(array.value(1), "dog");
+        assert_eq!(array.values().as_slice(), b"catdog");
+        assert_eq!(array.value_offsets(), &[0, 3, 6]);
+    }
+}
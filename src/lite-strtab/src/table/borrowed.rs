@@ -0,0 +1,106 @@
+//! Tables whose string bytes live in borrowed memory.
+
+use core::marker::PhantomData;
+use core::str;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTableIter};
+
+/// Immutable string table that borrows its byte buffer and owns its offsets.
+///
+/// Produced by [`crate::BufferStringTableBuilder::build`], so string data can
+/// stay in caller-provided memory such as a memory-mapped file. Lookups behave
+/// like [`crate::StringTable`]'s.
+pub struct BorrowedStringTable<
+    'a,
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    bytes: &'a [u8],
+    offsets: Box<[O], A>,
+    _id: PhantomData<I>,
+}
+
+impl<
+        'a,
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > BorrowedStringTable<'a, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates a table from parts that already uphold the table invariants.
+    #[inline]
+    pub(crate) fn from_parts_unchecked(bytes: &'a [u8], offsets: Box<[O], A>) -> Self {
+        Self {
+            bytes,
+            offsets,
+            _id: PhantomData,
+        }
+    }
+
+    /// Number of strings in the table.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` when the table has no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string for a given ID.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: StringId<I>) -> Option<&'a str> {
+        self.get_by_index(id.into_usize())
+    }
+
+    /// Returns the string at a raw index, without constructing a [`StringId`].
+    #[inline]
+    #[must_use]
+    pub fn get_by_index(&self, index: usize) -> Option<&'a str> {
+        if index < self.len() {
+            let start = self.offsets[index].to_usize();
+            // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+            let end = self.offsets[index + 1]
+                .to_usize()
+                .saturating_sub(usize::from(NULL_PADDED));
+            // SAFETY: Table invariants guarantee this range is in bounds and valid UTF-8.
+            Some(unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(start..end)) })
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over all strings in ID order.
+    #[inline]
+    pub fn iter(&self) -> StringTableIter<'_, O, NULL_PADDED> {
+        StringTableIter::new(self.bytes, &self.offsets)
+    }
+
+    /// Returns the borrowed byte buffer.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the offset table, including the final sentinel.
+    #[inline]
+    #[must_use]
+    pub fn offsets(&self) -> &[O] {
+        &self.offsets
+    }
+}
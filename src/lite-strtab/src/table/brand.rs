@@ -0,0 +1,79 @@
+//! Lookups that check which table an ID came from.
+
+use crate::allocator::*;
+use crate::{BrandedStringId, Offset, StringId, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Tags `id` as belonging to this table.
+    ///
+    /// For IDs that did not come from
+    /// [`StringTableBuilder::try_push_branded`](crate::StringTableBuilder::try_push_branded),
+    /// such as ones read back from storage.
+    #[inline]
+    #[must_use]
+    pub fn brand(&self, id: StringId<I>) -> BrandedStringId<I> {
+        BrandedStringId::new(id, self.tag)
+    }
+
+    /// Returns the string for a branded ID.
+    ///
+    /// Same as [`Self::get`], but in debug builds panics if `id` was branded
+    /// by a different table. Tables built from one builder share its brand.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// let id = builder.try_push_branded("hello").unwrap();
+    /// let table = builder.build();
+    ///
+    /// assert_eq!(table.get_branded(id), Some("hello"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get_branded(&self, id: BrandedStringId<I>) -> Option<&str> {
+        debug_assert!(
+            id.tag() == self.tag,
+            "string ID was issued by a different table"
+        );
+        self.get(id.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StringTableBuilder;
+
+    #[test]
+    fn branded_ids_resolve_in_their_table() {
+        let mut builder = StringTableBuilder::new();
+        let branded = builder.try_push_branded("one").unwrap();
+        let plain = builder.try_push("two").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get_branded(branded), Some("one"));
+        assert_eq!(table.get_branded(table.brand(plain)), Some("two"));
+        assert_eq!(branded.id().into_usize(), 0);
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, target_has_atomic = "32"))]
+    #[should_panic(expected = "different table")]
+    fn branded_id_from_other_table_panics_in_debug() {
+        let mut builder = StringTableBuilder::new();
+        let id = builder.try_push_branded("one").unwrap();
+        let mut other = StringTableBuilder::new();
+        other.try_push("one").unwrap();
+
+        let _ = other.build().get_branded(id);
+    }
+}
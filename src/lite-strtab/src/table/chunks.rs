@@ -0,0 +1,113 @@
+//! Splitting the byte buffer into groups of whole entries.
+
+use core::ops::Range;
+
+use crate::{Allocator, Offset, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns an iterator over byte chunks that never split an entry.
+    ///
+    /// Each item is a slice of [`Self::as_bytes`] covering a contiguous group
+    /// of whole entries (including NUL terminators when `NULL_PADDED`) and the
+    /// range of string indices it covers. Groups are formed greedily and stay
+    /// within `target_bytes`, except that every chunk holds at least one entry,
+    /// so a single entry larger than `target_bytes` forms its own chunk.
+    ///
+    /// The index range is a `Range<usize>` because its end may equal
+    /// [`Self::len`], which need not fit in `I`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("ab").unwrap();
+    /// builder.try_push("cd").unwrap();
+    /// builder.try_push("efgh").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let mut chunks = table.entry_aligned_chunks(4);
+    /// assert_eq!(chunks.next(), Some((&b"abcd"[..], 0..2)));
+    /// assert_eq!(chunks.next(), Some((&b"efgh"[..], 2..3)));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    pub fn entry_aligned_chunks(
+        &self,
+        target_bytes: usize,
+    ) -> impl Iterator<Item = (&[u8], Range<usize>)> + '_ {
+        let mut start = 0;
+        core::iter::from_fn(move || {
+            let len = self.len();
+            if start >= len {
+                return None;
+            }
+
+            let first = start;
+            let byte_start = self.offsets[first].to_usize();
+            let mut end = first + 1;
+            while end < len && self.offsets[end + 1].to_usize() - byte_start <= target_bytes {
+                end += 1;
+            }
+            start = end;
+
+            let byte_end = self.offsets[end].to_usize();
+            Some((&self.bytes[byte_start..byte_end], first..end))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::StringTableBuilder;
+
+    #[test]
+    fn entry_aligned_chunks_keep_oversized_entries_whole() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("alpha").unwrap();
+        builder.try_push("be").unwrap();
+        builder.try_push("g").unwrap();
+        let table = builder.build();
+
+        let chunks: Vec<_> = table.entry_aligned_chunks(2).collect();
+        assert_eq!(
+            chunks,
+            [
+                (&b"alpha\0"[..], 0..1),
+                (&b"be\0"[..], 1..2),
+                (&b"g\0"[..], 2..3),
+            ]
+        );
+
+        let chunks: Vec<_> = table.entry_aligned_chunks(5).collect();
+        assert_eq!(chunks, [(&b"alpha\0"[..], 0..1), (&b"be\0g\0"[..], 1..3)]);
+    }
+
+    #[test]
+    fn entry_aligned_chunks_cover_table() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["a", "", "bcd", "ef", "", "ghij"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let chunks: Vec<_> = table.entry_aligned_chunks(3).collect();
+        let joined: Vec<u8> = chunks.iter().flat_map(|(b, _)| b.iter().copied()).collect();
+        assert_eq!(joined, table.as_bytes());
+        assert_eq!(chunks.first().unwrap().1.start, 0);
+        assert_eq!(chunks.last().unwrap().1.end, table.len());
+        assert!(chunks.windows(2).all(|w| w[0].1.end == w[1].1.start));
+
+        let empty = StringTableBuilder::new().build();
+        assert_eq!(empty.entry_aligned_chunks(16).count(), 0);
+    }
+}
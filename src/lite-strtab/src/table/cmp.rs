@@ -0,0 +1,179 @@
+//! Content-based equality and ordering.
+//!
+//! Tables compare by their logical strings, in ID order. Offset and byte
+//! layout, NUL terminators and alignment padding do not take part.
+
+use core::cmp::Ordering;
+
+use crate::allocator::*;
+use crate::{Offset, StringIndex, StringTable};
+
+/// Tables are equal when they hold the same strings in the same order.
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > PartialEq for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > Eq for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+}
+
+/// A table equals a slice holding the same strings in the same order.
+///
+/// Compares logical content, so NUL padding does not take part.
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::StringTableBuilder;
+///
+/// let mut builder = StringTableBuilder::new_null_padded();
+/// builder.try_push("cat").unwrap();
+/// builder.try_push("dog").unwrap();
+/// let table = builder.build();
+///
+/// assert_eq!(table, ["cat", "dog"][..]);
+/// assert_eq!(table, vec!["cat", "dog"]);
+/// assert_ne!(table, ["cat"][..]);
+/// ```
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > PartialEq<[&str]> for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn eq(&self, other: &[&str]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > PartialEq<alloc::vec::Vec<&str>> for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn eq(&self, other: &alloc::vec::Vec<&str>) -> bool {
+        *self == other[..]
+    }
+}
+
+/// Orders tables lexicographically by their strings, as slices of `&str` do.
+///
+/// The first differing string decides; if one table is a prefix of the
+/// other, the shorter table is less. An empty table orders before every
+/// non-empty one. Comparison is over logical content, so NUL padding does
+/// not affect it.
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > PartialOrd for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > Ord for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use core::cmp::Ordering;
+
+    use crate::allocator::{Global, Vec};
+    use crate::{StringTable, StringTableBuilder};
+
+    fn table<const NULL_PADDED: bool>(values: &[&str]) -> StringTable<u32, u16, NULL_PADDED> {
+        let mut builder = StringTableBuilder::new_in(Global);
+        for value in values {
+            builder.try_push(value).unwrap();
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn eq_compares_logical_strings() {
+        assert!(table::<false>(&["ab", "c"]) == table::<false>(&["ab", "c"]));
+        assert!(table::<false>(&["ab", "c"]) != table::<false>(&["a", "bc"]));
+        assert!(table::<true>(&["", ""]) != table::<true>(&[""]));
+
+        // Alignment padding past the sentinel is not content.
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"ab\0\0\0");
+        let mut offsets = Vec::new_in(Global);
+        offsets.extend_from_slice(&[0u32, 3]);
+        let padded = StringTable::<u32, u16, true>::from_parts_unchecked(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+        );
+        assert!(padded == table::<true>(&["ab"]));
+    }
+
+    #[test]
+    fn eq_compares_against_str_slices() {
+        assert_eq!(table::<true>(&["ab", ""]), ["ab", ""][..]);
+        assert_eq!(table::<false>(&[]), [][..]);
+        assert_ne!(table::<true>(&["ab", ""]), ["ab"][..]);
+        assert_ne!(table::<false>(&["ab"]), ["ab", ""][..]);
+        assert_ne!(table::<false>(&["a", "bc"]), alloc::vec!["ab", "c"]);
+    }
+
+    #[test]
+    fn ord_is_lexicographic_over_strings() {
+        let empty = table::<true>(&[]);
+        let a = table::<true>(&["a"]);
+        let a_b = table::<true>(&["a", "b"]);
+        let b = table::<true>(&["b"]);
+
+        assert_eq!(empty.cmp(&a), Ordering::Less);
+        assert_eq!(a.cmp(&a_b), Ordering::Less);
+        assert_eq!(a_b.cmp(&b), Ordering::Less);
+        assert_eq!(b.cmp(&table::<true>(&["b"])), Ordering::Equal);
+
+        let set: BTreeSet<_> = [b, a_b, empty, a].into_iter().collect();
+        let expected: [&[&str]; 4] = [&[], &["a"], &["a", "b"], &["b"]];
+        assert_eq!(set.len(), 4);
+        assert!(set
+            .iter()
+            .zip(expected)
+            .all(|(table, strings)| table.eq_strings(strings)));
+    }
+}
@@ -0,0 +1,394 @@
+//! Rebuilding a table with narrower offset and ID types.
+
+use crate::allocator::*;
+use crate::{Offset, StringIndex, StringTable};
+
+/// Returns the width in bits of the narrowest unsigned integer holding `value`.
+///
+/// Widths above the pointer width are never returned: `usize` cannot exceed
+/// them, and they do not implement [`Offset`] or [`StringIndex`] there.
+#[inline]
+fn narrowest_bits(value: usize) -> u32 {
+    match value {
+        0..=0xFF => 8,
+        #[cfg(target_pointer_width = "16")]
+        _ => 16,
+        #[cfg(not(target_pointer_width = "16"))]
+        0x100..=0xFFFF => 16,
+        #[cfg(target_pointer_width = "32")]
+        _ => 32,
+        #[cfg(target_pointer_width = "64")]
+        0x1_0000..=0xFFFF_FFFF => 32,
+        #[cfg(target_pointer_width = "64")]
+        _ => 64,
+    }
+}
+
+macro_rules! compact_table {
+    ($($(#[$meta:meta])* ($variant:ident, $offset:ty, $id:ty, $offset_bits:literal, $id_bits:literal)),+ $(,)?) => {
+        /// A [`StringTable`] rebuilt by [`StringTable::compact`] with the
+        /// narrowest offset and ID types that hold its data.
+        ///
+        /// Variants are named after their offset and ID widths in bits:
+        /// `O16I8` holds a `StringTable<u16, u8, NULL_PADDED, A>`. Match on
+        /// the variant to get the concrete table. Variants with types wider
+        /// than the target's pointer width are not available.
+        pub enum CompactTable<
+            const NULL_PADDED: bool = false,
+            A: Allocator + Clone = Global,
+            const TERMINATOR: u8 = 0,
+        > {
+            $(
+                #[doc = concat!(
+                    "Table with [`", stringify!($offset), "`] offsets and [`",
+                    stringify!($id), "`] IDs."
+                )]
+                $(#[$meta])*
+                $variant(StringTable<$offset, $id, NULL_PADDED, A, TERMINATOR>),
+            )+
+        }
+
+        impl<const NULL_PADDED: bool, A: Allocator + Clone, const TERMINATOR: u8>
+            CompactTable<NULL_PADDED, A, TERMINATOR>
+        {
+            /// Number of strings in the table.
+            #[inline]
+            pub fn len(&self) -> usize {
+                match self {
+                    $($(#[$meta])* Self::$variant(table) => table.len(),)+
+                }
+            }
+
+            /// Returns `true` when the table contains no strings.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns the string at a raw `usize` index.
+            ///
+            /// See [`StringTable::get_by_index`].
+            #[inline]
+            pub fn get_by_index(&self, index: usize) -> Option<&str> {
+                match self {
+                    $($(#[$meta])* Self::$variant(table) => table.get_by_index(index),)+
+                }
+            }
+
+            /// Returns the contiguous byte storage.
+            #[inline]
+            pub fn as_bytes(&self) -> &[u8] {
+                match self {
+                    $($(#[$meta])* Self::$variant(table) => table.as_bytes(),)+
+                }
+            }
+
+            /// Returns the name of the chosen offset type, e.g. `"u8"`.
+            #[inline]
+            pub fn offset_type(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* Self::$variant(_) => <$offset as Offset>::TYPE_NAME,)+
+                }
+            }
+
+            /// Returns the name of the chosen ID type, e.g. `"u8"`.
+            #[inline]
+            pub fn id_type(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* Self::$variant(_) => <$id as StringIndex>::TYPE_NAME,)+
+                }
+            }
+        }
+
+        impl<O: Offset, I: StringIndex, const NULL_PADDED: bool, A: Allocator + Clone, const TERMINATOR: u8>
+            StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+        {
+            /// Rebuilds the table with the narrowest offset and ID types that
+            /// fit its data.
+            ///
+            /// The byte buffer is moved, not copied; only the offset table is
+            /// re-encoded. Alignment padding past the sentinel is dropped,
+            /// since it may not fit the narrower offset type. IDs keep their values, so existing
+            /// [`crate::StringId`]s can be converted to the new ID type.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// use lite_strtab::{CompactTable, StringTableBuilder};
+            ///
+            /// let mut builder = StringTableBuilder::new();
+            /// builder.try_push("hello").unwrap();
+            ///
+            /// let CompactTable::O8I8(table) = builder.build().compact() else {
+            ///     panic!("expected u8 offsets and IDs");
+            /// };
+            /// assert_eq!(table.get_by_index(0), Some("hello"));
+            /// ```
+            pub fn compact(self) -> CompactTable<NULL_PADDED, A, TERMINATOR> {
+                let offset_bits = narrowest_bits(self.sentinel().to_usize());
+                let id_bits = narrowest_bits(self.len().saturating_sub(1));
+                match (offset_bits, id_bits) {
+                    $($(#[$meta])* ($offset_bits, $id_bits) => CompactTable::$variant(self.rebuild()),)+
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+compact_table!(
+    (O8I8, u8, u8, 8, 8),
+    (O8I16, u8, u16, 8, 16),
+    #[cfg(not(target_pointer_width = "16"))]
+    (O8I32, u8, u32, 8, 32),
+    #[cfg(target_pointer_width = "64")]
+    (O8I64, u8, u64, 8, 64),
+    (O16I8, u16, u8, 16, 8),
+    (O16I16, u16, u16, 16, 16),
+    #[cfg(not(target_pointer_width = "16"))]
+    (O16I32, u16, u32, 16, 32),
+    #[cfg(target_pointer_width = "64")]
+    (O16I64, u16, u64, 16, 64),
+    #[cfg(not(target_pointer_width = "16"))]
+    (O32I8, u32, u8, 32, 8),
+    #[cfg(not(target_pointer_width = "16"))]
+    (O32I16, u32, u16, 32, 16),
+    #[cfg(not(target_pointer_width = "16"))]
+    (O32I32, u32, u32, 32, 32),
+    #[cfg(target_pointer_width = "64")]
+    (O32I64, u32, u64, 32, 64),
+    #[cfg(target_pointer_width = "64")]
+    (O64I8, u64, u8, 64, 8),
+    #[cfg(target_pointer_width = "64")]
+    (O64I16, u64, u16, 64, 16),
+    #[cfg(target_pointer_width = "64")]
+    (O64I32, u64, u32, 64, 32),
+    #[cfg(target_pointer_width = "64")]
+    (O64I64, u64, u64, 64, 64),
+);
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns the names of the narrowest primitive offset and ID types that
+    /// would hold this table's data, e.g. `("u16", "u8")`.
+    ///
+    /// These are the types [`Self::compact`] picks. Use this to choose `O`
+    /// and `I` for tables of a similar size without rebuilding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// for _ in 0..50 {
+    ///     builder.try_push(&"x".repeat(200)).unwrap();
+    /// }
+    /// assert_eq!(builder.build().recommended_types(), ("u16", "u8"));
+    /// ```
+    pub fn recommended_types(&self) -> (&'static str, &'static str) {
+        let name = |bits| match bits {
+            8 => "u8",
+            16 => "u16",
+            #[cfg(not(target_pointer_width = "16"))]
+            32 => "u32",
+            #[cfg(target_pointer_width = "64")]
+            64 => "u64",
+            _ => unreachable!(),
+        };
+        (
+            name(narrowest_bits(self.sentinel().to_usize())),
+            name(narrowest_bits(self.len().saturating_sub(1))),
+        )
+    }
+
+    /// Copies the table into one with a narrower (or wider) offset type.
+    ///
+    /// Returns [`None`] when the string data is too long for `O2`. The bytes
+    /// up to the sentinel are copied with the table's allocator, dropping any
+    /// alignment padding, and the offsets re-encoded; IDs are unchanged. Use [`Self::compact`] to pick the narrowest types
+    /// automatically and avoid the copy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{Global, StringTable, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::<u32, u16>::new_in(Global);
+    /// builder.try_push("hello").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let shrunk: StringTable<u8, u16> = table.shrink_offset_type().unwrap();
+    /// assert_eq!(shrunk.offsets(), &[0u8, 5]);
+    /// assert_eq!(shrunk.get_by_index(0), Some("hello"));
+    /// ```
+    pub fn shrink_offset_type<O2: Offset>(
+        &self,
+    ) -> Option<StringTable<O2, I, NULL_PADDED, A, TERMINATOR>> {
+        let sentinel = self.sentinel().to_usize();
+        O2::try_from_usize(sentinel)?;
+        let mut bytes = Vec::with_capacity_in(sentinel, self.allocator().clone());
+        bytes.extend_from_slice(&self.bytes[..sentinel]);
+
+        let table = StringTable::from_parts_tagged(
+            bytes.into_boxed_slice(),
+            self.convert_offsets(),
+            self.tag,
+        );
+        debug_assert!(table.validate().is_ok());
+        Some(table)
+    }
+
+    /// Moves the bytes into a table with different offset and ID types.
+    ///
+    /// Bytes past the sentinel are dropped. The caller must have checked
+    /// that the sentinel fits in `O2` and every ID fits in `I2`.
+    fn rebuild<O2: Offset, I2: StringIndex>(
+        self,
+    ) -> StringTable<O2, I2, NULL_PADDED, A, TERMINATOR> {
+        let offsets = self.convert_offsets();
+        let sentinel = self.sentinel().to_usize();
+        let bytes = if self.bytes.len() == sentinel {
+            self.bytes
+        } else {
+            let mut bytes = self.bytes.into_vec();
+            bytes.truncate(sentinel);
+            bytes.into_boxed_slice()
+        };
+        let table = StringTable::from_parts_unchecked(bytes, offsets);
+        debug_assert!(table.validate().is_ok());
+        table
+    }
+
+    /// Re-encodes the offset table as `O2`.
+    ///
+    /// The caller must have checked that the sentinel fits in `O2`.
+    fn convert_offsets<O2: Offset>(&self) -> Box<[O2], A> {
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), self.allocator().clone());
+        offsets.extend(self.offsets.iter().map(|offset| {
+            let offset = offset.to_usize();
+            debug_assert!(O2::try_from_usize(offset).is_some());
+            // SAFETY: Every offset is at most the sentinel, which fits in `O2`.
+            unsafe { O2::try_from_usize(offset).unwrap_unchecked() }
+        }));
+        offsets.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{CompactTable, StringTable, StringTableBuilder};
+
+    #[test]
+    fn compact_small_table_uses_u8_types() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["alpha", "beta", "gamma"] {
+            builder.try_push(value).unwrap();
+        }
+
+        let CompactTable::O8I8(table) = builder.build().compact() else {
+            panic!("expected u8 offsets and IDs");
+        };
+        assert!(table.iter().eq(["alpha", "beta", "gamma"]));
+        assert_eq!(table.offsets(), &[0u8, 6, 11, 17]);
+    }
+
+    #[test]
+    fn compact_widens_only_what_does_not_fit() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push(&"x".repeat(300)).unwrap();
+        let compact = builder.build().compact();
+        assert!(matches!(compact, CompactTable::O16I8(_)));
+        assert_eq!((compact.offset_type(), compact.id_type()), ("u16", "u8"));
+
+        let mut builder = StringTableBuilder::new();
+        for _ in 0..257 {
+            builder.try_push("").unwrap();
+        }
+        let compact = builder.build().compact();
+        assert!(matches!(compact, CompactTable::O8I16(_)));
+        assert_eq!(compact.len(), 257);
+        assert_eq!(compact.get_by_index(256), Some(""));
+
+        let compact = StringTableBuilder::new().build().compact();
+        assert!(matches!(compact, CompactTable::O8I8(_)));
+        assert!(compact.is_empty());
+    }
+
+    #[test]
+    fn recommended_types_match_compact() {
+        let mut builder = StringTableBuilder::new();
+        for _ in 0..257 {
+            builder.try_push("").unwrap();
+        }
+        let table = builder.build();
+        assert_eq!(table.recommended_types(), ("u8", "u16"));
+        let compact = table.compact();
+        assert_eq!((compact.offset_type(), compact.id_type()), ("u8", "u16"));
+
+        assert_eq!(StringTable::empty().recommended_types(), ("u8", "u8"));
+
+        let mut builder = StringTableBuilder::<u16, u16, true>::new_in(Global);
+        builder.try_push("ab").unwrap();
+        let aligned = builder.build_aligned::<256>().unwrap();
+        assert_eq!(aligned.recommended_types(), ("u8", "u8"));
+        let compact = aligned.compact();
+        assert_eq!((compact.offset_type(), compact.id_type()), ("u8", "u8"));
+    }
+
+    #[test]
+    fn shrink_offset_type_checks_byte_length() {
+        let mut builder = StringTableBuilder::<u32, u16, true>::new_in(Global);
+        builder.try_push(&"x".repeat(200)).unwrap();
+        builder.try_push("y").unwrap();
+        let table = builder.build();
+
+        let Some(shrunk) = table.shrink_offset_type::<u8>() else {
+            panic!("203 bytes fit u8 offsets");
+        };
+        assert_eq!(shrunk.offsets(), &[0u8, 201, 203]);
+        assert!(shrunk.iter().eq(table.iter()));
+        assert_eq!(shrunk.as_bytes(), table.as_bytes());
+
+        builder = StringTableBuilder::new_in(Global);
+        builder.try_push(&"x".repeat(256)).unwrap();
+        let table: StringTable<u32, u16, true> = builder.build();
+        assert!(table.shrink_offset_type::<u8>().is_none());
+        assert!(table.shrink_offset_type::<u16>().is_some());
+    }
+
+    #[test]
+    fn compact_ignores_alignment_padding() {
+        let mut builder = StringTableBuilder::<u16, u16, true>::new_in(Global);
+        builder.try_push("ab").unwrap();
+        let table = builder.build_aligned::<256>().unwrap();
+
+        let CompactTable::O8I8(compact) = table.compact() else {
+            panic!("3 bytes of data fit u8 offsets");
+        };
+        assert_eq!(compact.as_bytes(), b"ab\0");
+        assert_eq!(compact.validate(), Ok(()));
+    }
+
+    #[test]
+    fn shrink_offset_type_drops_alignment_padding() {
+        let mut builder = StringTableBuilder::<u16, u16, true>::new_in(Global);
+        builder.try_push("ab").unwrap();
+        let table = builder.build_aligned::<256>().unwrap();
+        assert_eq!(table.as_bytes().len(), 256);
+
+        let Some(shrunk) = table.shrink_offset_type::<u8>() else {
+            panic!("3 bytes of data fit u8 offsets");
+        };
+        assert_eq!(shrunk.as_bytes(), b"ab\0");
+        assert_eq!(shrunk.offsets(), &[0u8, 3]);
+        assert_eq!(shrunk.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,243 @@
+//! Conversions between a table and other owned representations.
+
+use alloc::string::String;
+
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringIndex, StringTable, StringTableBuilder};
+
+/// Builds a table from a slice of strings, in order.
+///
+/// Returns the same errors as [`StringTableBuilder::try_push`].
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::StringTable;
+///
+/// let table = StringTable::<u32, u32>::try_from(&["cat", "dog"][..]).unwrap();
+/// assert_eq!(table.get_by_index(1), Some("dog"));
+/// ```
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool, const TERMINATOR: u8> TryFrom<&[&str]>
+    for StringTable<O, I, NULL_PADDED, Global, TERMINATOR>
+{
+    type Error = Error;
+
+    #[inline]
+    fn try_from(values: &[&str]) -> Result<Self> {
+        Self::try_from_strs(values)
+    }
+}
+
+/// Builds a table from owned strings, in order.
+///
+/// The strings are copied into the table's buffer and then dropped. Returns
+/// the same errors as [`StringTableBuilder::try_push`].
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::StringTable;
+///
+/// let names = vec![String::from("cat"), String::from("dog")];
+/// let table = StringTable::<u32, u32>::try_from(names).unwrap();
+/// assert_eq!(table.len(), 2);
+/// ```
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool, const TERMINATOR: u8>
+    TryFrom<alloc::vec::Vec<String>> for StringTable<O, I, NULL_PADDED, Global, TERMINATOR>
+{
+    type Error = Error;
+
+    #[inline]
+    fn try_from(values: alloc::vec::Vec<String>) -> Result<Self> {
+        Self::try_from_strs(&values)
+    }
+}
+
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool, const TERMINATOR: u8>
+    StringTable<O, I, NULL_PADDED, Global, TERMINATOR>
+{
+    /// Pushes `values` into a builder sized to fit them exactly.
+    fn try_from_strs<S: AsRef<str>>(values: &[S]) -> Result<Self> {
+        let bytes = values
+            .iter()
+            .map(|value| value.as_ref().len() + usize::from(NULL_PADDED))
+            .fold(0usize, usize::saturating_add);
+        let mut builder = StringTableBuilder::with_capacity_in(values.len(), bytes, Global);
+        for value in values {
+            builder.try_push(value.as_ref())?;
+        }
+        Ok(builder.build())
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Copies the table into `allocator`.
+    ///
+    /// Makes exactly two allocations in `allocator`, one for the bytes and one
+    /// for the offsets. Alignment padding is copied too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{Global, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("hello").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let copy = table.clone_in(Global);
+    /// assert!(copy == table);
+    /// ```
+    pub fn clone_in<A2: Allocator + Clone>(
+        &self,
+        allocator: A2,
+    ) -> StringTable<O, I, NULL_PADDED, A2, TERMINATOR> {
+        let mut bytes = Vec::with_capacity_in(self.bytes.len(), allocator.clone());
+        bytes.extend_from_slice(&self.bytes);
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), allocator);
+        offsets.extend_from_slice(&self.offsets);
+
+        let table = StringTable::from_parts_tagged(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+            self.tag,
+        );
+        debug_assert!(table.validate().is_ok());
+        table
+    }
+
+    /// Copies every string into its own [`Box<str, A2>`](Box) allocated in
+    /// `allocator`, then drops the table.
+    ///
+    /// The result is indexed by ID. This makes one allocation per string plus
+    /// one for the vector, all in `allocator`; it is the opposite trade-off to
+    /// the table's single buffer.
+    pub fn into_boxed_strs_in<A2: Allocator + Clone>(self, allocator: A2) -> Vec<Box<str, A2>, A2> {
+        let mut strings = Vec::with_capacity_in(self.len(), allocator.clone());
+        for value in self.iter() {
+            let mut bytes = Vec::with_capacity_in(value.len(), allocator.clone());
+            bytes.extend_from_slice(value.as_bytes());
+            let (raw, allocator) = Box::into_raw_with_allocator(bytes.into_boxed_slice());
+            // SAFETY: `raw` owns a copy of `value`, which is valid UTF-8, and
+            // `str` has the same layout as `[u8]`.
+            strings.push(unsafe { Box::from_raw_in(raw as *mut str, allocator) });
+        }
+        strings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::alloc::Layout;
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::allocator::{AllocError, Allocator, Global};
+    use crate::{Error, StringTable, StringTableBuilder};
+
+    /// Delegates to [`Global`] and counts live and total allocations.
+    #[derive(Clone, Copy)]
+    pub(crate) struct CountingAllocator<'a> {
+        pub(crate) allocations: &'a Cell<usize>,
+        pub(crate) live: &'a Cell<usize>,
+    }
+
+    unsafe impl Allocator for CountingAllocator<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocations.set(self.allocations.get() + 1);
+            self.live.set(self.live.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn into_boxed_strs_in_allocates_each_string_in_target() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["alpha", "beta", "gamma"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let (allocations, live) = (Cell::new(0), Cell::new(0));
+        let allocator = CountingAllocator {
+            allocations: &allocations,
+            live: &live,
+        };
+        let strings = table.into_boxed_strs_in(allocator);
+
+        assert_eq!(strings.len(), 3);
+        assert_eq!(&*strings[2], "gamma");
+        // One allocation for the vector plus one per string.
+        assert_eq!(allocations.get(), 4);
+        assert_eq!(live.get(), 4);
+
+        drop(strings);
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn clone_in_and_to_bytes_in_use_target_allocator() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["alpha", "", "gamma"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let (allocations, live) = (Cell::new(0), Cell::new(0));
+        let allocator = CountingAllocator {
+            allocations: &allocations,
+            live: &live,
+        };
+        let copy = table.clone_in(allocator);
+        assert_eq!((allocations.get(), live.get()), (2, 2));
+        assert!(copy.validate().is_ok());
+        assert!(copy.iter().eq(table.iter()));
+        assert_eq!(copy.offsets(), table.offsets());
+
+        let bytes = copy.to_bytes_in(allocator);
+        assert_eq!((allocations.get(), live.get()), (3, 3));
+        assert_eq!(&bytes[..], &table.to_bytes()[..]);
+
+        drop((copy, bytes));
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn try_from_slice_reports_overflow() {
+        let values = [""; 257];
+        let Err(error) = StringTable::<u32, u8>::try_from(&values[..]) else {
+            panic!("expected too many strings");
+        };
+        assert_eq!(
+            error,
+            Error::TooManyStrings {
+                strings: 257,
+                id_type: "u8",
+            }
+        );
+
+        let owned: Vec<String> = ["a", "bc"]
+            .iter()
+            .map(|value| String::from(*value))
+            .collect();
+        let Ok(table) = StringTable::<u32, u16, true>::try_from(owned) else {
+            panic!("conversion failed");
+        };
+        assert_eq!(table.as_bytes(), b"a\0bc\0");
+    }
+}
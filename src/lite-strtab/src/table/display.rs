@@ -0,0 +1,121 @@
+//! Formatting tables and string IDs for diagnostics.
+
+use core::fmt;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns a value that formats `id` along with its string, for
+    /// diagnostics.
+    ///
+    /// Formats as `<id>:"<string>"`, with the string escaped like [`Debug`]
+    /// output, or as `<id>:<out-of-range>` when `id` is not in the table. The
+    /// string is only looked up when formatted.
+    ///
+    /// [`Debug`]: fmt::Debug
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{StringId, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// let id = builder.try_push("foo").unwrap();
+    /// let table = builder.build();
+    ///
+    /// assert_eq!(table.display_id(id).to_string(), "0:\"foo\"");
+    /// assert_eq!(table.display_id(StringId::new(3)).to_string(), "3:<out-of-range>");
+    /// ```
+    #[inline]
+    pub fn display_id(&self, id: StringId<I>) -> impl fmt::Display + '_ {
+        DisplayId { table: self, id }
+    }
+}
+
+/// [`fmt::Display`] helper returned by [`StringTable::display_id`].
+struct DisplayId<
+    'a,
+    O: Offset,
+    I: StringIndex,
+    const NULL_PADDED: bool,
+    A: Allocator + Clone,
+    const TERMINATOR: u8,
+> {
+    table: &'a StringTable<O, I, NULL_PADDED, A, TERMINATOR>,
+    id: StringId<I>,
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > fmt::Display for DisplayId<'_, O, I, NULL_PADDED, A, TERMINATOR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.id.into_usize();
+        match self.table.get(self.id) {
+            Some(value) => write!(f, "{index}:{value:?}"),
+            None => write!(f, "{index}:<out-of-range>"),
+        }
+    }
+}
+
+/// Formats the table as a list of its strings, like a slice of `&str`.
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > fmt::Debug for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn display_id_escapes_and_reports_out_of_range() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("").unwrap();
+        let quoted = builder.try_push("say \"hi\"\n").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.display_id(StringId::new(0)).to_string(), "0:\"\"");
+        assert_eq!(
+            table.display_id(quoted).to_string(),
+            "1:\"say \\\"hi\\\"\\n\""
+        );
+        assert_eq!(
+            table.display_id(StringId::new(2)).to_string(),
+            "2:<out-of-range>"
+        );
+    }
+
+    #[test]
+    fn debug_lists_strings() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("a").unwrap();
+        builder.try_push("").unwrap();
+        let table = builder.build();
+
+        assert_eq!(format!("{table:?}"), "[\"a\", \"\"]");
+    }
+}
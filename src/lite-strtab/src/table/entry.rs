@@ -0,0 +1,137 @@
+//! Lookups that keep a string's ID and location together.
+
+use core::fmt;
+use core::ops::Range;
+use core::str;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+/// One string of a [`StringTable`], returned by [`StringTable::entry`].
+///
+/// Bundles the ID with the table's storage, so the string and its byte range
+/// are available together. Both are computed when asked for.
+pub struct Entry<'a, O: Offset = u32, I = u16, const NULL_PADDED: bool = false> {
+    bytes: &'a [u8],
+    /// The entry's start offset and sentinel-side end offset.
+    bounds: &'a [O; 2],
+    id: StringId<I>,
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns an [`Entry`] for `id`, or [`None`] when it is out of range.
+    ///
+    /// Use it where the string, its ID and its byte range are needed together;
+    /// [`Self::get`] stays the simplest lookup.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("foo").unwrap();
+    /// let id = builder.try_push("bar").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let entry = table.entry(id).unwrap();
+    /// assert_eq!(entry.id(), id);
+    /// assert_eq!(entry.as_str(), "bar");
+    /// assert_eq!(entry.range(), 3..6);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn entry(&self, id: StringId<I>) -> Option<Entry<'_, O, I, NULL_PADDED>> {
+        let index = id.into_usize();
+        let bounds = self.offsets.get(index..index.checked_add(2)?)?;
+        Some(Entry {
+            bytes: &self.bytes,
+            // The slice has exactly two elements.
+            bounds: bounds.try_into().ok()?,
+            id,
+        })
+    }
+}
+
+impl<'a, O: Offset, I: StringIndex, const NULL_PADDED: bool> Entry<'a, O, I, NULL_PADDED> {
+    /// Returns the entry's ID.
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> StringId<I> {
+        self.id
+    }
+
+    /// Returns the entry's byte range in
+    /// [`StringTable::as_bytes`], excluding the NUL terminator in null-padded
+    /// mode, like [`StringTable::byte_range`].
+    #[inline]
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        let [start, end] = self.bounds.map(Offset::to_usize);
+        // Const generic: default (`false`) folds the subtraction away.
+        start..end - usize::from(NULL_PADDED)
+    }
+
+    /// Returns the entry's string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        let range = self.range();
+        // SAFETY: The bounds are adjacent table offsets, so table invariants
+        // guarantee the range is in bounds and valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(range)) }
+    }
+}
+
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool> Clone for Entry<'_, O, I, NULL_PADDED> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool> Copy for Entry<'_, O, I, NULL_PADDED> {}
+
+impl<O: Offset, I: StringIndex, const NULL_PADDED: bool> fmt::Debug
+    for Entry<'_, O, I, NULL_PADDED>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("id", &self.id.into_usize())
+            .field("value", &self.as_str())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn entry_matches_get_and_byte_range() {
+        let mut builder = StringTableBuilder::<u16, u8, true>::new_in(Global);
+        let ids = [
+            builder.try_push("one").unwrap(),
+            builder.try_push("").unwrap(),
+            builder.try_push("three").unwrap(),
+        ];
+        let table = builder.build();
+
+        for id in ids {
+            let entry = table.entry(id).unwrap();
+            assert_eq!(entry.id(), id);
+            assert_eq!(Some(entry.as_str()), table.get(id));
+            assert_eq!(Some(entry.range()), table.byte_range(id));
+        }
+        assert!(table.entry(StringId::new(3)).is_none());
+        assert!(table.entry(StringId::new(u8::MAX)).is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Front-coded serialization.
+//!
+//! Each entry is encoded as:
+//!
+//! - LEB128 varint: bytes shared with the previous entry's prefix
+//! - LEB128 varint: length of the remaining suffix
+//! - the suffix bytes
+//!
+//! The first entry shares nothing. The entry count is implied by the blob
+//! length, and NUL terminators of null-padded tables are not stored.
+
+use core::str;
+
+use super::order::common_prefix_len;
+use super::varint::{read_varint, varint_len, write_varint};
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringIndex, StringTable, StringTableBuilder};
+
+impl StringTable<u32, u16, false, Global> {
+    /// Decodes a blob produced by [`StringTable::to_front_coded`] using the
+    /// global allocator.
+    ///
+    /// See [`StringTable::from_front_coded_in`] for errors.
+    #[inline]
+    pub fn from_front_coded(blob: &[u8]) -> Result<Self> {
+        Self::from_front_coded_in(blob, Global)
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Encodes the table in front-coded form.
+    ///
+    /// Each entry stores only the suffix that differs from the previous entry,
+    /// so tables sorted lexicographically (such as file paths) typically shrink
+    /// considerably. Any order round-trips correctly; unsorted input just
+    /// shares fewer prefixes. [`Self::greedy_prefix_order`] can help choose an
+    /// order when IDs may be reassigned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{StringTable, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("sound/bgm.awb").unwrap();
+    /// builder.try_push("sound/se.awb").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let blob = table.to_front_coded();
+    /// assert!(blob.len() < table.as_bytes().len());
+    /// let decoded = StringTable::from_front_coded(&blob).unwrap();
+    /// assert!(decoded.iter().eq(table.iter()));
+    /// ```
+    pub fn to_front_coded(&self) -> Vec<u8, A> {
+        let mut blob = Vec::new_in(self.allocator().clone());
+        let mut previous: &[u8] = &[];
+        for value in self.iter() {
+            let value = value.as_bytes();
+            let shared = common_prefix_len(previous, value);
+            write_varint(&mut blob, shared);
+            write_varint(&mut blob, value.len() - shared);
+            blob.extend_from_slice(&value[shared..]);
+            previous = value;
+        }
+        blob
+    }
+
+    /// Returns the byte length of [`Self::to_front_coded`]'s output without
+    /// materializing it.
+    ///
+    /// Compare against [`Self::as_bytes`] plus [`Self::offsets`] to decide
+    /// whether front coding is worth it for this table.
+    pub fn front_coded_size(&self) -> usize {
+        let mut size = 0;
+        let mut previous: &[u8] = &[];
+        for value in self.iter() {
+            let value = value.as_bytes();
+            let shared = common_prefix_len(previous, value);
+            let suffix_len = value.len() - shared;
+            size += varint_len(shared) + varint_len(suffix_len) + suffix_len;
+            previous = value;
+        }
+        size
+    }
+
+    /// Decodes a blob produced by [`Self::to_front_coded`] with a custom
+    /// allocator.
+    ///
+    /// Returns [`Error::InvalidEncoding`] for truncated or malformed input,
+    /// including entries that do not decode to valid UTF-8, and the usual
+    /// capacity errors if the decoded table does not fit `O` or `I`.
+    pub fn from_front_coded_in(blob: &[u8], allocator: A) -> Result<Self> {
+        let mut builder = StringTableBuilder::<O, I, NULL_PADDED, A, TERMINATOR>::new_in(allocator);
+        let mut previous = 0..0;
+        let mut position = 0;
+
+        while position < blob.len() {
+            let entry_position = position;
+            let shared = read_varint(blob, &mut position)?;
+            let suffix_len = read_varint(blob, &mut position)?;
+            if shared > previous.len() {
+                return Err(Error::InvalidEncoding {
+                    position: entry_position,
+                    reason: "shared prefix is longer than the previous string",
+                });
+            }
+            let suffix = position
+                .checked_add(suffix_len)
+                .and_then(|end| blob.get(position..end))
+                .ok_or(Error::InvalidEncoding {
+                    position,
+                    reason: "suffix extends past the end of the input",
+                })?;
+
+            let id = builder.next_id()?;
+            let start = builder.bytes.len();
+            builder
+                .bytes
+                .extend_from_within(previous.start..previous.start + shared);
+            builder.bytes.extend_from_slice(suffix);
+            if str::from_utf8(&builder.bytes[start..]).is_err() {
+                builder.bytes.truncate(start);
+                return Err(Error::InvalidEncoding {
+                    position: entry_position,
+                    reason: "string is not valid UTF-8",
+                });
+            }
+            builder.try_commit(start, id)?;
+
+            previous = start..start + shared + suffix_len;
+            position += suffix_len;
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{Error, StringTable, StringTableBuilder};
+
+    fn table_of(values: &[&str]) -> StringTable {
+        let mut builder = StringTableBuilder::new();
+        for value in values {
+            builder.try_push(value).unwrap();
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn front_coded_roundtrip_sorted() {
+        let table = table_of(&[
+            "",
+            "model/player.gmd",
+            "model/player_alt.gmd",
+            "sound/bgm.awb",
+            "sound/bgm_boss.awb",
+        ]);
+
+        let blob = table.to_front_coded();
+        assert!(blob.len() < table.as_bytes().len());
+        let decoded = StringTable::from_front_coded(&blob).unwrap();
+        assert_eq!(decoded.offsets(), table.offsets());
+        assert!(decoded.iter().eq(table.iter()));
+    }
+
+    #[test]
+    fn front_coded_roundtrip_unsorted_and_null_padded() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["zeta", "caf\u{e9}", "cafe", "", "caf\u{e9}s", "alpha"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let blob = table.to_front_coded();
+        let decoded = StringTable::<u32, u16, true>::from_front_coded_in(&blob, Global).unwrap();
+        assert_eq!(decoded.offsets(), table.offsets());
+        assert_eq!(decoded.as_bytes(), table.as_bytes());
+
+        let empty = StringTable::empty();
+        assert!(empty.to_front_coded().is_empty());
+        assert!(StringTable::from_front_coded(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn front_coded_size_matches_encoding() {
+        let long = "x".repeat(300);
+        let longer = alloc::format!("{long}{long}");
+        for values in [
+            &[][..],
+            &["", ""][..],
+            &["alpha", "alphabet", "beta", "alp"][..],
+            &[long.as_str(), longer.as_str(), "x"][..],
+        ] {
+            let table = table_of(values);
+            assert_eq!(table.front_coded_size(), table.to_front_coded().len());
+        }
+    }
+
+    #[test]
+    fn from_front_coded_rejects_malformed_input() {
+        let reason = |blob: &[u8]| match StringTable::from_front_coded(blob) {
+            Err(Error::InvalidEncoding { reason, .. }) => reason,
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("malformed input decoded successfully"),
+        };
+
+        assert_eq!(reason(&[0x80]), "truncated varint");
+        assert_eq!(
+            reason(&[0, 3, b'a']),
+            "suffix extends past the end of the input"
+        );
+        assert_eq!(
+            reason(&[0, 1, b'a', 2, 0]),
+            "shared prefix is longer than the previous string"
+        );
+        assert_eq!(reason(&[0, 1, 0xC3]), "string is not valid UTF-8");
+        assert_eq!(reason(&[0xFF; 11]), "varint does not fit in usize");
+    }
+}
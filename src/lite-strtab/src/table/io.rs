@@ -0,0 +1,189 @@
+//! Offset table I/O with explicit byte order.
+//!
+//! Each offset is written using `size_of::<O>()` bytes in the requested byte
+//! order, independent of the host's native representation.
+
+use core::mem::size_of;
+use std::io::{self, Read, Write};
+
+use crate::allocator::*;
+use crate::{Offset, StringIndex, StringTable};
+
+/// Bytes buffered per `write_all`/`read_exact` call.
+const CHUNK_BYTES: usize = 512;
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Writes every offset, including the sentinel, in little-endian order.
+    #[inline]
+    pub fn write_offsets_le<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_offsets::<W, false>(writer)
+    }
+
+    /// Writes every offset, including the sentinel, in big-endian order.
+    #[inline]
+    pub fn write_offsets_be<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_offsets::<W, true>(writer)
+    }
+
+    /// Reads `count` little-endian offsets written by [`Self::write_offsets_le`].
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] for values that do not fit in `O`.
+    #[inline]
+    pub fn read_offsets_le<R: Read>(
+        reader: &mut R,
+        count: usize,
+        allocator: A,
+    ) -> io::Result<Box<[O], A>> {
+        Self::read_offsets::<R, false>(reader, count, allocator)
+    }
+
+    /// Reads `count` big-endian offsets written by [`Self::write_offsets_be`].
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] for values that do not fit in `O`.
+    #[inline]
+    pub fn read_offsets_be<R: Read>(
+        reader: &mut R,
+        count: usize,
+        allocator: A,
+    ) -> io::Result<Box<[O], A>> {
+        Self::read_offsets::<R, true>(reader, count, allocator)
+    }
+
+    fn write_offsets<W: Write, const BIG_ENDIAN: bool>(&self, writer: &mut W) -> io::Result<()> {
+        let width = size_of::<O>();
+        let mut chunk = [0u8; CHUNK_BYTES];
+        let mut used = 0;
+
+        for offset in self.offsets.iter() {
+            if used + width > CHUNK_BYTES {
+                writer.write_all(&chunk[..used])?;
+                used = 0;
+            }
+
+            let value = offset.to_usize() as u64;
+            let encoded = if BIG_ENDIAN {
+                &value.to_be_bytes()[8 - width..]
+            } else {
+                &value.to_le_bytes()[..width]
+            };
+            chunk[used..used + width].copy_from_slice(encoded);
+            used += width;
+        }
+
+        writer.write_all(&chunk[..used])
+    }
+
+    fn read_offsets<R: Read, const BIG_ENDIAN: bool>(
+        reader: &mut R,
+        count: usize,
+        allocator: A,
+    ) -> io::Result<Box<[O], A>> {
+        let width = size_of::<O>();
+        let per_chunk = CHUNK_BYTES / width;
+        let mut chunk = [0u8; CHUNK_BYTES];
+        let mut offsets = Vec::with_capacity_in(count, allocator);
+
+        while offsets.len() < count {
+            let batch = per_chunk.min(count - offsets.len());
+            let encoded = &mut chunk[..batch * width];
+            reader.read_exact(encoded)?;
+
+            for encoded in encoded.chunks_exact(width) {
+                let mut raw = [0u8; 8];
+                let value = if BIG_ENDIAN {
+                    raw[8 - width..].copy_from_slice(encoded);
+                    u64::from_be_bytes(raw)
+                } else {
+                    raw[..width].copy_from_slice(encoded);
+                    u64::from_le_bytes(raw)
+                };
+
+                let offset = usize::try_from(value)
+                    .ok()
+                    .and_then(O::try_from_usize)
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "offset does not fit in the offset type",
+                        )
+                    })?;
+                offsets.push(offset);
+            }
+        }
+
+        Ok(offsets.into_boxed_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::allocator::Global;
+    use crate::{StringTable, StringTableBuilder};
+
+    fn sample() -> StringTable<u16> {
+        let mut builder = StringTableBuilder::<u16>::new_in(Global);
+        builder.try_push("cat").unwrap();
+        builder.try_push(&"x".repeat(300)).unwrap();
+        builder.build()
+    }
+
+    #[test]
+    fn little_endian_roundtrip() {
+        let table = sample();
+        let mut encoded = Vec::new();
+        table.write_offsets_le(&mut encoded).unwrap();
+        assert_eq!(encoded, [0, 0, 3, 0, 0x2F, 0x01]);
+
+        let decoded =
+            StringTable::<u16>::read_offsets_le(&mut encoded.as_slice(), 3, Global).unwrap();
+        assert_eq!(&*decoded, table.offsets());
+    }
+
+    #[test]
+    fn big_endian_roundtrip() {
+        let table = sample();
+        let mut encoded = Vec::new();
+        table.write_offsets_be(&mut encoded).unwrap();
+        assert_eq!(encoded, [0, 0, 0, 3, 0x01, 0x2F]);
+
+        let decoded =
+            StringTable::<u16>::read_offsets_be(&mut encoded.as_slice(), 3, Global).unwrap();
+        assert_eq!(&*decoded, table.offsets());
+    }
+
+    #[test]
+    fn roundtrip_spans_multiple_chunks() {
+        let mut builder = StringTableBuilder::new();
+        for _ in 0..1000 {
+            builder.try_push("ab").unwrap();
+        }
+        let table = builder.build();
+
+        let mut encoded = Vec::new();
+        table.write_offsets_be(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), 1001 * 4);
+
+        let decoded =
+            StringTable::<u32>::read_offsets_be(&mut encoded.as_slice(), 1001, Global).unwrap();
+        assert_eq!(&*decoded, table.offsets());
+    }
+
+    #[test]
+    fn read_reports_truncated_input() {
+        let encoded = [0u8, 0, 3];
+        let result = StringTable::<u16>::read_offsets_le(&mut encoded.as_slice(), 2, Global);
+        assert_eq!(
+            result.unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+}
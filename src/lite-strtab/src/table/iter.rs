@@ -0,0 +1,592 @@
+//! Iterators over the strings of a [`crate::StringTable`].
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+use core::str;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+/// Iterator returned by [`crate::StringTable::iter`].
+///
+/// Iterating forwards reads one offset per string: the end of each string is
+/// cached as the start of the next.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct StringTableIter<'a, O: Offset = u32, const NULL_PADDED: bool = false> {
+    bytes: &'a [u8],
+    cur_offset: *const O,
+    max_offset: *const O,
+    /// Value of `*cur_offset`, i.e. the start of the next front string.
+    start: usize,
+    remaining: usize,
+    _offsets: PhantomData<&'a [O]>,
+}
+
+impl<'a, O: Offset, const NULL_PADDED: bool> StringTableIter<'a, O, NULL_PADDED> {
+    /// Creates an iterator over the table with `bytes` and `offsets`.
+    #[inline]
+    pub(crate) fn new(bytes: &'a [u8], offsets: &'a [O]) -> Self {
+        let strings = offsets.len().saturating_sub(1);
+        let cur_offset = offsets.as_ptr();
+
+        Self {
+            bytes,
+            cur_offset,
+            // SAFETY: `strings` is at most `offsets.len() - 1`, so this stays
+            // in-bounds and may equal `cur_offset` for an empty iterator.
+            max_offset: unsafe { cur_offset.add(strings) },
+            start: offsets.first().map_or(0, |offset| offset.to_usize()),
+            remaining: strings,
+            _offsets: PhantomData,
+        }
+    }
+
+    /// Returns the next string without advancing the iterator.
+    ///
+    /// Reads the front string's end offset; the position and
+    /// [`Self::remaining`] are unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("a").unwrap();
+    /// builder.try_push("b").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let mut iter = table.iter();
+    /// assert_eq!(iter.peek(), Some("a"));
+    /// assert_eq!(iter.remaining(), 2);
+    /// assert_eq!(iter.next(), Some("a"));
+    /// assert_eq!(iter.peek(), Some("b"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<&'a str> {
+        if self.cur_offset != self.max_offset {
+            // SAFETY: A string remains, so `cur_offset + 1` is at most
+            // `max_offset` and in bounds, and `start..end` is its range.
+            let end = unsafe { (*self.cur_offset.add(1)).to_usize() };
+            Some(unsafe { Self::to_str(Self::slice(self.bytes, self.start, end)) })
+        } else {
+            None
+        }
+    }
+
+    /// Number of strings not yet yielded from either end.
+    ///
+    /// Same as [`ExactSizeIterator::len`], without importing the trait.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns the logical bytes of the string spanning `start..end`.
+    ///
+    /// # Safety
+    ///
+    /// `start..end` must be the stored range of a string of the table that
+    /// `bytes` belongs to.
+    #[inline(always)]
+    unsafe fn slice(bytes: &'a [u8], start: usize, end: usize) -> &'a [u8] {
+        // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+        let logical_end = end.saturating_sub(usize::from(NULL_PADDED));
+        debug_assert!(logical_end >= start);
+
+        // SAFETY: Pool invariants guarantee this slice is in bounds.
+        unsafe { bytes.get_unchecked(start..logical_end) }
+    }
+
+    /// Converts logical string bytes into a `&str`.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be returned by [`Self::slice`].
+    #[inline(always)]
+    unsafe fn to_str(value: &'a [u8]) -> &'a str {
+        // SAFETY: Pool invariants guarantee every string is valid UTF-8.
+        unsafe { str::from_utf8_unchecked(value) }
+    }
+
+    /// Takes the front string's bytes, reading only its end offset.
+    ///
+    /// # Safety
+    ///
+    /// At least one string must remain (`cur_offset != max_offset`).
+    #[inline(always)]
+    unsafe fn take_front(&mut self) -> &'a [u8] {
+        // SAFETY: A string remains, so `cur_offset + 1` is at most
+        // `max_offset` and in bounds.
+        self.cur_offset = unsafe { self.cur_offset.add(1) };
+        let end = unsafe { (*self.cur_offset).to_usize() };
+        let value = unsafe { Self::slice(self.bytes, self.start, end) };
+        self.start = end;
+        self.remaining -= 1;
+        value
+    }
+
+    /// Takes the back string's bytes.
+    ///
+    /// # Safety
+    ///
+    /// At least one string must remain (`cur_offset != max_offset`).
+    #[inline(always)]
+    unsafe fn take_back(&mut self) -> &'a [u8] {
+        // SAFETY: A string remains, so `max_offset - 1` and `max_offset` are
+        // both valid.
+        let end = unsafe { (*self.max_offset).to_usize() };
+        self.max_offset = unsafe { self.max_offset.sub(1) };
+        let start = unsafe { (*self.max_offset).to_usize() };
+        self.remaining -= 1;
+        unsafe { Self::slice(self.bytes, start, end) }
+    }
+}
+
+impl<'a, O: Offset, const NULL_PADDED: bool> Iterator for StringTableIter<'a, O, NULL_PADDED> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_offset != self.max_offset {
+            // SAFETY: `cur_offset != max_offset` guarantees a string remains.
+            Some(unsafe { Self::to_str(self.take_front()) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.remaining
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // The iterator is consumed, so `remaining` does not need to be kept
+        // in sync; walk the offset pointer and cached start alone.
+        let mut acc = init;
+        let mut cur_offset = self.cur_offset;
+        let mut start = self.start;
+        while cur_offset != self.max_offset {
+            // SAFETY: `cur_offset != max_offset`, so `cur_offset + 1` is valid.
+            cur_offset = unsafe { cur_offset.add(1) };
+            let end = unsafe { (*cur_offset).to_usize() };
+            acc = f(acc, unsafe {
+                Self::to_str(Self::slice(self.bytes, start, end))
+            });
+            start = end;
+        }
+        acc
+    }
+
+    // Overriding `try_fold` requires the unstable `Try` trait. On stable,
+    // `try_for_each`/`try_fold` fall back to the default loop over `next`.
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        let mut acc = init;
+        while self.cur_offset != self.max_offset {
+            // SAFETY: `cur_offset != max_offset` guarantees a string remains.
+            // Taking it before calling `f` leaves a short-circuited iterator
+            // positioned after the element that stopped it.
+            let value = unsafe { Self::to_str(self.take_front()) };
+            acc = f(acc, value)?;
+        }
+        R::from_output(acc)
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> DoubleEndedIterator
+    for StringTableIter<'_, O, NULL_PADDED>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cur_offset != self.max_offset {
+            // SAFETY: `cur_offset != max_offset` guarantees a string remains.
+            Some(unsafe { Self::to_str(self.take_back()) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> ExactSizeIterator for StringTableIter<'_, O, NULL_PADDED> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> FusedIterator for StringTableIter<'_, O, NULL_PADDED> {}
+
+// SAFETY: The raw pointers only read the borrowed offset table, as a
+// `&'a [O]` would. `O: Offset` implies `O: Sync`, so sharing or sending
+// that borrow across threads is sound.
+unsafe impl<O: Offset, const NULL_PADDED: bool> Send for StringTableIter<'_, O, NULL_PADDED> {}
+// SAFETY: See `Send` above; `&self` methods only read `remaining` and the
+// shared offset table.
+unsafe impl<O: Offset, const NULL_PADDED: bool> Sync for StringTableIter<'_, O, NULL_PADDED> {}
+
+/// Iterator returned by [`crate::StringTable::iter_bytes`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct StringTableBytesIter<'a, O: Offset = u32, const NULL_PADDED: bool = false> {
+    inner: StringTableIter<'a, O, NULL_PADDED>,
+}
+
+impl<'a, O: Offset, const NULL_PADDED: bool> StringTableBytesIter<'a, O, NULL_PADDED> {
+    #[inline]
+    pub(super) fn new(inner: StringTableIter<'a, O, NULL_PADDED>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, O: Offset, const NULL_PADDED: bool> Iterator for StringTableBytesIter<'a, O, NULL_PADDED> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = &mut self.inner;
+        if inner.cur_offset != inner.max_offset {
+            // SAFETY: `cur_offset != max_offset` guarantees a string remains.
+            Some(unsafe { inner.take_front() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.remaining
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> DoubleEndedIterator
+    for StringTableBytesIter<'_, O, NULL_PADDED>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let inner = &mut self.inner;
+        if inner.cur_offset != inner.max_offset {
+            // SAFETY: `cur_offset != max_offset` guarantees a string remains.
+            Some(unsafe { inner.take_back() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> ExactSizeIterator
+    for StringTableBytesIter<'_, O, NULL_PADDED>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.remaining
+    }
+}
+
+impl<O: Offset, const NULL_PADDED: bool> FusedIterator
+    for StringTableBytesIter<'_, O, NULL_PADDED>
+{
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns an iterator over the strings from `id` onwards.
+    ///
+    /// Starts directly at `id` in O(1), unlike `iter().skip(n)`, which steps
+    /// over every skipped string. An `id` past the end yields nothing.
+    #[inline]
+    pub fn iter_from(&self, id: StringId<I>) -> StringTableIter<'_, O, NULL_PADDED> {
+        let start = id.into_usize().min(self.len());
+        StringTableIter::new(&self.bytes, &self.offsets[start..])
+    }
+
+    /// Returns an iterator over the strings with IDs in `range`, or [`None`]
+    /// if `range.end > self.len()`.
+    ///
+    /// Like [`Self::iter_from`], but also stops at `range.end`. A range with
+    /// `start >= end` yields nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// for value in ["a", "b", "c", "d"] {
+    ///     builder.try_push(value).unwrap();
+    /// }
+    /// let table = builder.build();
+    ///
+    /// assert!(table.slice(1..3).unwrap().eq(["b", "c"]));
+    /// assert!(table.slice(2..5).is_none());
+    /// ```
+    #[inline]
+    pub fn slice(&self, range: Range<usize>) -> Option<StringTableIter<'_, O, NULL_PADDED>> {
+        if range.end > self.len() {
+            return None;
+        }
+        let start = range.start.min(range.end);
+        Some(StringTableIter::new(
+            &self.bytes,
+            &self.offsets[start..=range.end],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::{Global, Vec};
+    use crate::{StringId, StringTable, StringTableBuilder};
+
+    #[test]
+    fn iter_from_matches_skip() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "bc", "d"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        for k in 0..=6u16 {
+            let from: Vec<_> = table.iter_from(StringId::new(k)).collect();
+            let skipped: Vec<_> = table.iter().skip(usize::from(k)).collect();
+            assert_eq!(from, skipped);
+        }
+        assert!(table.iter_from(StringId::new(1)).rev().eq(["d", "bc", ""]));
+    }
+
+    #[test]
+    fn peek_does_not_advance() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["x", ""] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let mut iter = table.iter();
+        assert_eq!(iter.peek(), Some("x"));
+        assert_eq!(iter.peek(), Some("x"));
+        assert_eq!(iter.remaining(), 2);
+        assert_eq!(iter.next_back(), Some(""));
+        assert_eq!(iter.peek(), Some("x"));
+        assert_eq!(iter.next(), Some("x"));
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.remaining(), 0);
+    }
+
+    #[test]
+    fn slice_bounds_ranges() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "bc", "d"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let Some(full) = table.slice(0..4) else {
+            panic!("full range is in bounds");
+        };
+        assert_eq!(full.len(), 4);
+        assert!(full.eq(table.iter()));
+        assert!(table.slice(1..3).unwrap().rev().eq(["bc", ""]));
+        assert_eq!(table.slice(2..2).unwrap().len(), 0);
+        assert_eq!(table.slice(4..4).unwrap().len(), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..1;
+        assert_eq!(table.slice(reversed).unwrap().len(), 0);
+        assert!(table.slice(0..5).is_none());
+        assert!(table.slice(5..5).is_none());
+    }
+
+    #[test]
+    fn try_for_each_stops_at_first_error() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abcd");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.extend_from_slice(&[0u32, 1, 2, 3, 4]);
+
+        let table = StringTable::<u32, u16>::from_parts_unchecked(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+        );
+
+        let mut iter = table.iter();
+        let mut visited = 0usize;
+        let result = iter.try_for_each(|value| {
+            visited += 1;
+            if value == "b" {
+                Err(value)
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(result, Err("b"));
+        assert_eq!(visited, 2);
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some("c"));
+    }
+
+    #[test]
+    fn fold_visits_all_strings() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abcd");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.extend_from_slice(&[0u32, 1, 1, 4]);
+
+        let table = StringTable::<u32, u16>::from_parts_unchecked(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+        );
+
+        let total = table.iter().fold(0usize, |acc, value| acc + value.len());
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn count_and_last_respect_advancement() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "ccc", "dd"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        assert_eq!(table.iter().count(), 4);
+        assert_eq!(table.iter().last(), Some("dd"));
+
+        let mut iter = table.iter();
+        iter.next();
+        assert_eq!(iter.nth(1), Some("ccc"));
+        assert_eq!(iter.count(), 1);
+
+        let mut iter = table.iter();
+        iter.next_back();
+        assert_eq!(iter.last(), Some("ccc"));
+        assert_eq!(StringTable::empty().iter().last(), None);
+    }
+
+    #[test]
+    fn iter_bytes_matches_iter() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["ab", "", "caf\u{e9}"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        assert!(table.iter_bytes().eq(table.iter().map(str::as_bytes)));
+        let mut bytes = table.iter_bytes();
+        assert_eq!(bytes.len(), 3);
+        assert_eq!(bytes.next_back(), Some("caf\u{e9}".as_bytes()));
+        assert_eq!(bytes.next(), Some(&b"ab"[..]));
+        assert_eq!(bytes.count(), 1);
+        assert_eq!(StringTable::empty().iter_bytes().next(), None);
+    }
+
+    #[test]
+    fn iter_rev_walks_backwards() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "ccc", "dd"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let mut rev = table.iter_rev();
+        assert_eq!(rev.len(), 4);
+        assert_eq!(rev.next(), Some("dd"));
+        assert_eq!(rev.len(), 3);
+        assert!(rev.eq(["ccc", "", "a"]));
+
+        let mut iter = table.iter();
+        assert_eq!(iter.next(), Some("a"));
+        assert_eq!(iter.next_back(), Some("dd"));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some("ccc"));
+        assert_eq!(iter.next(), Some(""));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(StringTable::empty().iter_rev().next(), None);
+    }
+
+    #[test]
+    fn cached_start_survives_mixed_directions() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "bcd", "ef"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let mut iter = table.iter();
+        assert_eq!(iter.next_back(), Some("ef"));
+        assert_eq!(iter.next(), Some("a"));
+        assert_eq!(iter.next(), Some(""));
+        assert_eq!(iter.next_back(), Some("bcd"));
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+        let mut iter = table.iter();
+        iter.next();
+        assert!(iter
+            .fold(Vec::new_in(Global), |mut acc, value| {
+                acc.push(value);
+                acc
+            })
+            .eq(&["", "bcd", "ef"]));
+
+        let empty = StringTable::empty();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter().fold(0, |count, _| count + 1), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn iterators_can_move_to_other_threads() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["a", "bb", "ccc"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let mut iter = table.iter();
+        iter.next();
+        let bytes = table.iter_bytes();
+        let (total, count) = std::thread::scope(|scope| {
+            let total = scope.spawn(move || iter.map(str::len).sum::<usize>());
+            let count = scope.spawn(move || bytes.count());
+            (total.join().unwrap(), count.join().unwrap())
+        });
+        assert_eq!((total, count), (5, 3));
+    }
+}
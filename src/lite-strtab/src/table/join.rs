@@ -0,0 +1,267 @@
+//! Relational-style queries across two tables.
+
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+/// Marks the end of a chain in the join index.
+const CHAIN_END: usize = usize::MAX;
+
+/// Set differences between two tables, returned by [`StringTable::diff`].
+///
+/// Each side is the set of distinct strings of one table. The iterators
+/// borrow from the tables and yield strings in unspecified order.
+pub struct TableDiff<'a> {
+    left: HashSet<&'a str>,
+    right: HashSet<&'a str>,
+}
+
+impl<'a> TableDiff<'a> {
+    /// Strings present only in the table `diff` was called on, e.g. removed
+    /// ones when comparing an old table against a new one.
+    pub fn only_in_self(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.left.difference(&self.right).copied()
+    }
+
+    /// Strings present only in the other table, e.g. added ones.
+    pub fn only_in_other(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.right.difference(&self.left).copied()
+    }
+
+    /// Strings present in both tables.
+    pub fn in_both(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.left.intersection(&self.right).copied()
+    }
+
+    /// Returns `true` when both tables hold the same set of strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.left == self.right
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns every `(self_id, other_id)` pair whose strings are equal.
+    ///
+    /// Duplicates on either side produce every matching combination, like a
+    /// relational inner join. Pairs are ordered by `self_id`, then `other_id`.
+    ///
+    /// This builds a temporary hash index over `other`, running in
+    /// `O(self.len() + other.len() + matches)` time.
+    pub fn inner_join(&self, other: &Self) -> Vec<(StringId<I>, StringId<I>), A> {
+        // Index `other` as a multimap: each distinct string maps to the first
+        // and last index of a chain threaded through `next`. Appending at the
+        // tail keeps each chain in ascending ID order.
+        let mut chains: HashMap<&str, (usize, usize)> = HashMap::with_capacity(other.len());
+        let mut next = Vec::with_capacity_in(other.len(), Global);
+        for (index, value) in other.iter().enumerate() {
+            next.push(CHAIN_END);
+            chains
+                .entry(value)
+                .and_modify(|(_, last)| {
+                    next[*last] = index;
+                    *last = index;
+                })
+                .or_insert((index, index));
+        }
+
+        let mut pairs = Vec::new_in(self.allocator().clone());
+        for (index, value) in self.iter().enumerate() {
+            if let Some(&(mut matched, _)) = chains.get(value) {
+                while matched != CHAIN_END {
+                    pairs.push((Self::id_at(index), Self::id_at(matched)));
+                    matched = next[matched];
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Compares the distinct strings of `self` and `other` as sets.
+    ///
+    /// Comparison is by logical content, so tables with different offset
+    /// types, ID types or NUL padding can be compared; duplicates and order
+    /// are ignored. Building the two hash sets takes
+    /// `O(self.len() + other.len())` time, and each [`TableDiff`] iterator
+    /// runs in time linear in the size of the set it walks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut old = StringTableBuilder::new();
+    /// for value in ["kept", "removed"] {
+    ///     old.try_push(value).unwrap();
+    /// }
+    /// let old = old.build();
+    /// let mut new = StringTableBuilder::new();
+    /// for value in ["added", "kept"] {
+    ///     new.try_push(value).unwrap();
+    /// }
+    /// let new = new.build();
+    ///
+    /// let diff = old.diff(&new);
+    /// assert!(diff.only_in_self().eq(["removed"]));
+    /// assert!(diff.only_in_other().eq(["added"]));
+    /// assert!(diff.in_both().eq(["kept"]));
+    /// ```
+    pub fn diff<'a, O2, I2, const NULL_PADDED2: bool, A2, const TERMINATOR2: u8>(
+        &'a self,
+        other: &'a StringTable<O2, I2, NULL_PADDED2, A2, TERMINATOR2>,
+    ) -> TableDiff<'a>
+    where
+        O2: Offset,
+        I2: StringIndex,
+        A2: Allocator + Clone,
+    {
+        TableDiff {
+            left: self.iter().collect(),
+            right: other.iter().collect(),
+        }
+    }
+
+    /// Returns a content hash for every entry, indexed by ID.
+    ///
+    /// The hash depends only on the string, not on its position, so entries of
+    /// independently built tables can be correlated by comparing hashes. IDs
+    /// are only stable across processes if `S` is deterministic: randomly
+    /// seeded hashers such as [`hashbrown::DefaultHashBuilder`] produce
+    /// different values per instance.
+    ///
+    /// Distinct strings can collide. With a good 64-bit hash the chance of any
+    /// collision among `n` strings is roughly `n² / 2⁶⁵`; compare the strings
+    /// themselves where a false match would be harmful.
+    pub fn content_ids<S: BuildHasher + Default>(&self) -> Box<[u64], A> {
+        let hasher = S::default();
+        let mut ids = Vec::with_capacity_in(self.len(), self.allocator().clone());
+        ids.extend(self.iter().map(|value| hasher.hash_one(value)));
+        ids.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StringTableBuilder;
+
+    #[test]
+    fn inner_join_produces_cartesian_matches_for_duplicates() {
+        let mut left = StringTableBuilder::new();
+        for value in ["a", "b", "a", "c"] {
+            left.try_push(value).unwrap();
+        }
+        let left = left.build();
+
+        let mut right = StringTableBuilder::new();
+        for value in ["a", "d", "a", "b"] {
+            right.try_push(value).unwrap();
+        }
+        let right = right.build();
+
+        let pairs: alloc::vec::Vec<(u16, u16)> = left
+            .inner_join(&right)
+            .iter()
+            .map(|&(a, b)| (a.into_raw(), b.into_raw()))
+            .collect();
+        assert_eq!(pairs, [(0, 0), (0, 2), (1, 3), (2, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn inner_join_without_matches_is_empty() {
+        let mut left = StringTableBuilder::new();
+        left.try_push("a").unwrap();
+        let left = left.build();
+
+        let right = StringTableBuilder::new().build();
+        assert!(left.inner_join(&right).is_empty());
+        assert!(right.inner_join(&left).is_empty());
+    }
+
+    #[test]
+    fn diff_ignores_padding_duplicates_and_order() {
+        let mut left = StringTableBuilder::new();
+        for value in ["b", "a", "b", "c"] {
+            left.try_push(value).unwrap();
+        }
+        let left = left.build();
+
+        let mut right = StringTableBuilder::<u8, u8, true>::new_in(crate::Global);
+        for value in ["d", "c", "a"] {
+            right.try_push(value).unwrap();
+        }
+        let right = right.build();
+
+        let diff = left.diff(&right);
+        assert_eq!(sorted(diff.only_in_self()), ["b"]);
+        assert_eq!(sorted(diff.only_in_other()), ["d"]);
+        assert_eq!(sorted(diff.in_both()), ["a", "c"]);
+        assert!(!diff.is_empty());
+        assert!(left.diff(&left).is_empty());
+    }
+
+    #[test]
+    fn content_ids_are_independent_of_position() {
+        let mut left = StringTableBuilder::new();
+        for value in ["alpha", "beta", "gamma"] {
+            left.try_push(value).unwrap();
+        }
+        let left = left.build();
+
+        let mut right = StringTableBuilder::new();
+        for value in ["gamma", "alpha", "beta"] {
+            right.try_push(value).unwrap();
+        }
+        let right = right.build();
+
+        let left_ids = left.content_ids::<Fnv>();
+        let right_ids = right.content_ids::<Fnv>();
+        assert_eq!(left_ids.len(), 3);
+        assert_eq!(left_ids[0], right_ids[1]);
+        assert_eq!(left_ids[2], right_ids[0]);
+
+        let mut left_sorted: alloc::vec::Vec<_> = left_ids.iter().copied().collect();
+        let mut right_sorted: alloc::vec::Vec<_> = right_ids.iter().copied().collect();
+        left_sorted.sort_unstable();
+        right_sorted.sort_unstable();
+        assert_eq!(left_sorted, right_sorted);
+    }
+
+    fn sorted<'a>(values: impl Iterator<Item = &'a str>) -> alloc::vec::Vec<&'a str> {
+        let mut values: alloc::vec::Vec<_> = values.collect();
+        values.sort_unstable();
+        values
+    }
+
+    /// Deterministic FNV-1a hasher, so IDs are reproducible across runs.
+    struct FnvHasher(u64);
+
+    impl Default for FnvHasher {
+        fn default() -> Self {
+            Self(0xcbf2_9ce4_8422_2325)
+        }
+    }
+
+    impl core::hash::Hasher for FnvHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+
+    type Fnv = core::hash::BuildHasherDefault<FnvHasher>;
+}
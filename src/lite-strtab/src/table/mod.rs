@@ -0,0 +1,634 @@
+//! Immutable string storage backed by one byte buffer and one offset table.
+//!
+//! The layout is:
+//!
+//! - `bytes`: all UTF-8 string bytes concatenated
+//! - `offsets`: start offset for each string, plus one final sentinel
+//! - strings are not NUL-terminated by default; boundaries come from offsets
+//!
+//! For `n` strings, `offsets.len() == n + 1`.
+//! String `i` is `bytes[offsets[i]..offsets[i + 1]]`.
+
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod borrowed;
+mod brand;
+mod chunks;
+mod cmp;
+mod compact;
+mod convert;
+mod display;
+mod entry;
+mod front_coded;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+mod join;
+mod order;
+mod packed;
+mod ranges;
+mod search;
+mod serialize;
+mod split;
+#[cfg(feature = "unicode-normalization")]
+mod unicode;
+mod validate;
+mod varint;
+
+#[cfg(feature = "rkyv")]
+pub use archive::{ArchivedStringTable, StringTableResolver};
+pub use borrowed::BorrowedStringTable;
+pub use compact::CompactTable;
+pub use entry::Entry;
+pub use iter::{StringTableBytesIter, StringTableIter};
+pub use join::TableDiff;
+pub use packed::PackedStringTable;
+pub use serialize::TableHeader;
+
+use core::fmt;
+use core::iter::Rev;
+use core::marker::PhantomData;
+use core::str;
+
+use crate::allocator::*;
+use crate::types::TableTag;
+use crate::{Offset, StringId, StringIndex};
+
+/// Alias for [`StringTable`].
+pub type StringPool<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A = Global,
+    const TERMINATOR: u8 = 0,
+> = StringTable<O, I, NULL_PADDED, A, TERMINATOR>;
+
+/// Alias for [`StringTableIter`].
+pub type StringPoolIter<'a, O = u32, const NULL_PADDED: bool = false> =
+    StringTableIter<'a, O, NULL_PADDED>;
+
+/// Immutable string storage.
+///
+/// All strings are stored in a single contiguous UTF-8 byte buffer.
+/// An offset table maps each [`StringId`] to a byte range.
+///
+/// The table is immutable once built. This keeps references returned by [`Self::get`]
+/// valid for the lifetime of `&self` and avoids mutation-related reallocation issues.
+///
+/// The offset table always contains one extra value at the end (a sentinel)
+/// equal to `bytes.len()`. This allows `get` to resolve a range with two
+/// offset reads. Tables from [`crate::StringTableBuilder::build_aligned`] are
+/// the exception: their byte buffer continues past the sentinel with NUL
+/// padding.
+///
+/// Generic parameters control capacity and metadata size:
+/// - `O` is the byte-offset type (see [`Offset`]). It bounds total UTF-8 bytes and costs
+///   `size_of::<O>()` per string inside the [`crate::StringTable`].
+/// - `I` is the string-ID type (see [`StringIndex`]) used by [`crate::StringId`].
+///   It limits string count and costs `size_of::<I>()` per stored ID field
+///   (table index) in your own structs.
+///
+/// The common choice is `O = u32, I = u16`: meaning `4 GiB` of UTF-8 data
+/// and `64Ki` entries per table.
+///
+/// This is 4 bytes per string offset in the table and 2 bytes
+/// per StringID (index into table) inside your structs.
+/// For comparison: `Box<str>` == 16 bytes, `String` == 24 bytes.
+///
+/// By default, inserted strings are not NUL-terminated.
+/// Set `NULL_PADDED = true` to store strings with a trailing `TERMINATOR`
+/// byte, NUL unless configured otherwise. Lookups strip exactly one byte, and
+/// validation checks that it equals `TERMINATOR`.
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::StringTableBuilder;
+///
+/// let mut builder = StringTableBuilder::new();
+/// let a = builder.try_push("cat").unwrap();
+/// let b = builder.try_push("dog").unwrap();
+///
+/// let table = builder.build();
+/// assert_eq!(table.get(a), Some("cat"));
+/// assert_eq!(table.get(b), Some("dog"));
+/// ```
+pub struct StringTable<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    bytes: Box<[u8], A>,
+    offsets: Box<[O], A>,
+    tag: TableTag,
+    _id: PhantomData<I>,
+}
+
+impl StringTable<u32, u16, false, Global> {
+    /// Creates an empty table using the global allocator.
+    #[inline]
+    pub fn empty() -> Self {
+        Self::empty_in(Global)
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates an empty table with a custom allocator.
+    pub fn empty_in(allocator: A) -> Self {
+        let bytes = Vec::new_in(allocator.clone()).into_boxed_slice();
+        let mut offsets = Vec::with_capacity_in(1, allocator);
+        offsets.push(zero_offset::<O>());
+
+        Self::from_parts_unchecked(bytes, offsets.into_boxed_slice())
+    }
+
+    #[inline]
+    pub(crate) fn from_parts_unchecked(bytes: Box<[u8], A>, offsets: Box<[O], A>) -> Self {
+        Self::from_parts_tagged(bytes, offsets, TableTag::new())
+    }
+
+    /// Like [`Self::from_parts_unchecked`], but keeps the brand of the builder
+    /// or table the parts came from, for tables whose IDs stay valid.
+    #[inline]
+    pub(crate) fn from_parts_tagged(
+        bytes: Box<[u8], A>,
+        offsets: Box<[O], A>,
+        tag: TableTag,
+    ) -> Self {
+        Self {
+            bytes,
+            offsets,
+            tag,
+            _id: PhantomData,
+        }
+    }
+
+    /// Returns the allocator backing this table's buffers.
+    #[inline]
+    pub(crate) fn allocator(&self) -> &A {
+        Box::allocator(&self.bytes)
+    }
+
+    /// Converts an in-bounds string index into its [`StringId`].
+    #[inline]
+    pub(crate) fn id_at(index: usize) -> StringId<I> {
+        debug_assert!(I::try_from_usize(index).is_some());
+        // SAFETY: The builder checked that every index below `len()` fits in `I`.
+        StringId::new(unsafe { I::try_from_usize(index).unwrap_unchecked() })
+    }
+
+    /// Number of strings in the table.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` when the table has no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string for a given ID.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: StringId<I>) -> Option<&str> {
+        self.get_by_index(id.into_usize())
+    }
+
+    /// Returns the string for a given ID, or `default` when it is out of
+    /// range.
+    #[inline]
+    #[must_use]
+    pub fn get_or<'a>(&'a self, id: StringId<I>, default: &'a str) -> &'a str {
+        self.get(id).unwrap_or(default)
+    }
+
+    /// Returns the string for a given ID, or `""` when it is out of range.
+    #[inline]
+    #[must_use]
+    #[doc(alias = "get_or_default")]
+    pub fn get_or_empty(&self, id: StringId<I>) -> &str {
+        self.get_or(id, "")
+    }
+
+    /// Returns the string at a raw index, without constructing a [`StringId`].
+    ///
+    /// Useful when indices come from deserialized data as plain integers.
+    /// Prefer [`Self::get`] when a typed ID is available.
+    #[inline]
+    #[must_use]
+    pub fn get_by_index(&self, index: usize) -> Option<&str> {
+        // Failure (None) is unlikely; users typically provide valid indices.
+        // Since likely/unlikely isn't stable, we structure this so the
+        // success path falls through without jumps, improving pipelining.
+        if index < self.len() {
+            // SAFETY: Bounds check above guarantees both offset entries exist.
+            let start = unsafe { self.offsets.get_unchecked(index) }.to_usize();
+            let end = unsafe { self.offsets.get_unchecked(index + 1) }.to_usize();
+            // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+            let logical_end = end.saturating_sub(usize::from(NULL_PADDED));
+            debug_assert!(logical_end >= start);
+
+            // SAFETY: Table invariants guarantee this range is in bounds and valid UTF-8.
+            let bytes = unsafe { self.bytes.get_unchecked(start..logical_end) };
+            // SAFETY: Invariants guarantee all ranges are valid UTF-8.
+            Some(unsafe { str::from_utf8_unchecked(bytes) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the strings for two IDs, or [`None`] if either is out of range.
+    ///
+    /// Checks both IDs against one [`Self::len`], then looks them up without
+    /// further bounds checks.
+    #[inline]
+    #[must_use]
+    pub fn get_pair(&self, a: StringId<I>, b: StringId<I>) -> Option<(&str, &str)> {
+        let len = self.len();
+        if a.into_usize() < len && b.into_usize() < len {
+            // SAFETY: Both IDs were checked against `len()` above.
+            Some(unsafe { (self.get_unchecked(a), self.get_unchecked(b)) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first string, or [`None`] if the table is empty.
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<&str> {
+        self.get_by_index(0)
+    }
+
+    /// Returns the last string, or [`None`] if the table is empty.
+    ///
+    /// Reads the last two offsets directly instead of iterating.
+    #[inline]
+    #[must_use]
+    pub fn last(&self) -> Option<&str> {
+        self.get_by_index(self.len().checked_sub(1)?)
+    }
+
+    /// Writes the string for `id` to `writer`.
+    ///
+    /// Returns [`None`] if `id` is out of range, otherwise the result of
+    /// [`fmt::Write::write_str`].
+    #[inline]
+    pub fn write_str_to<W: fmt::Write>(
+        &self,
+        id: StringId<I>,
+        writer: &mut W,
+    ) -> Option<fmt::Result> {
+        self.get(id).map(|value| writer.write_str(value))
+    }
+
+    /// Returns the string for a given ID without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `id` must be in bounds (`id < self.len()`).
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_unchecked(&self, id: StringId<I>) -> &str {
+        let index = id.into_usize();
+        let start = unsafe { self.offsets.get_unchecked(index) }.to_usize();
+        let end = unsafe { self.offsets.get_unchecked(index + 1) }.to_usize();
+        // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+        let logical_end = end.saturating_sub(usize::from(NULL_PADDED));
+        debug_assert!(logical_end >= start);
+        let bytes = unsafe { self.bytes.get_unchecked(start..logical_end) };
+
+        // SAFETY: Invariants guarantee all ranges are valid UTF-8.
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Returns the bytes of the string for a given ID.
+    ///
+    /// The same slice as [`Self::get`], as `&[u8]`; the NUL terminator is
+    /// excluded in null-padded mode. Useful for consumers such as hashers that
+    /// do not need a `&str`.
+    #[inline]
+    #[must_use]
+    pub fn bytes_of(&self, id: StringId<I>) -> Option<&[u8]> {
+        let range = self.byte_range(id)?;
+        // SAFETY: `byte_range` only returns in-bounds ranges.
+        Some(unsafe { self.bytes.get_unchecked(range) })
+    }
+
+    /// Returns the bytes of the string for a given ID without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `id` must be in bounds (`id < self.len()`).
+    #[inline]
+    #[must_use]
+    pub unsafe fn bytes_of_unchecked(&self, id: StringId<I>) -> &[u8] {
+        let index = id.into_usize();
+        let start = unsafe { self.offsets.get_unchecked(index) }.to_usize();
+        let end = unsafe { self.offsets.get_unchecked(index + 1) }.to_usize();
+        // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+        let logical_end = end.saturating_sub(usize::from(NULL_PADDED));
+        debug_assert!(logical_end >= start);
+        unsafe { self.bytes.get_unchecked(start..logical_end) }
+    }
+
+    /// Returns an iterator over all strings.
+    #[inline]
+    pub fn iter(&self) -> StringTableIter<'_, O, NULL_PADDED> {
+        StringTableIter::new(&self.bytes, &self.offsets)
+    }
+
+    /// Iterates over the strings alongside `data[id]`, for data kept in a
+    /// parallel slice indexed by [`StringId`].
+    ///
+    /// Yields `min(self.len(), data.len())` pairs. A `data` shorter than the
+    /// table is usually a bug, so it panics in debug builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("a").unwrap();
+    /// builder.try_push("b").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let weights = [1.5, 2.0];
+    /// assert!(table.zip_with(&weights).eq([("a", &1.5), ("b", &2.0)]));
+    /// ```
+    #[inline]
+    pub fn zip_with<'a, T>(
+        &'a self,
+        data: &'a [T],
+    ) -> impl ExactSizeIterator<Item = (&'a str, &'a T)> + 'a {
+        debug_assert!(
+            data.len() >= self.len(),
+            "data has {} entries for {} strings",
+            data.len(),
+            self.len()
+        );
+        self.iter().zip(data)
+    }
+
+    /// Returns an iterator over each string's bytes, in ID order.
+    ///
+    /// Yields the same slices as [`Self::iter`] without going through `&str`;
+    /// the NUL terminator is excluded in null-padded mode. Useful for feeding
+    /// entries to byte-oriented hashers.
+    #[inline]
+    pub fn iter_bytes(&self) -> StringTableBytesIter<'_, O, NULL_PADDED> {
+        StringTableBytesIter::new(self.iter())
+    }
+
+    /// Returns an iterator over all strings from the last ID to the first.
+    ///
+    /// Equivalent to `self.iter().rev()`.
+    #[inline]
+    pub fn iter_rev(&self) -> Rev<StringTableIter<'_, O, NULL_PADDED>> {
+        self.iter().rev()
+    }
+
+    /// Returns the contiguous byte storage, including any alignment padding.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns `true` if any stored string equals `value`.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, value: &str) -> bool {
+        self.iter().any(|item| item == value)
+    }
+
+    /// Returns `true` if `other` yields exactly the stored strings, in order.
+    ///
+    /// Stops at the first mismatch. A longer or shorter `other` compares
+    /// unequal.
+    #[must_use]
+    pub fn eq_strings<S: AsRef<str>, It: IntoIterator<Item = S>>(&self, other: It) -> bool {
+        let mut other = other.into_iter();
+        for value in self.iter() {
+            match other.next() {
+                Some(expected) if expected.as_ref() == value => {}
+                _ => return false,
+            }
+        }
+        other.next().is_none()
+    }
+
+    /// Returns the offset table, including the final sentinel.
+    #[inline]
+    #[must_use]
+    pub fn offsets(&self) -> &[O] {
+        &self.offsets
+    }
+}
+
+/// Returns the physical byte buffer, as [`StringTable::as_bytes`] does.
+///
+/// This includes NUL terminators in null-padded mode and any alignment
+/// padding. Use [`StringTable::byte_range`] for per-string logical slices.
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > AsRef<[u8]> for StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[inline]
+fn zero_offset<O: Offset>() -> O {
+    // SAFETY: All built-in integer implementations accept zero.
+    unsafe { O::try_from_usize(0).unwrap_unchecked() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{StringId, StringTable, StringTableBuilder};
+
+    #[test]
+    fn zip_with_pairs_strings_with_data() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["x", "y"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let data = [10, 20, 30];
+        let pairs = table.zip_with(&data);
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.eq([("x", &10), ("y", &20)]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "data has 1 entries for 2 strings")]
+    fn zip_with_rejects_short_data_in_debug() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["x", "y"] {
+            builder.try_push(value).unwrap();
+        }
+        let _ = builder.build().zip_with(&[0]).count();
+    }
+
+    #[test]
+    fn bytes_of_excludes_nul_terminator() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let a = builder.try_push("caf\u{e9}").unwrap();
+        let b = builder.try_push("").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.bytes_of(a), Some("caf\u{e9}".as_bytes()));
+        assert_eq!(table.bytes_of(b), Some(&b""[..]));
+        assert_eq!(table.bytes_of(StringId::new(2)), None);
+        // SAFETY: Both IDs were returned by the builder of this table.
+        unsafe {
+            assert_eq!(
+                table.bytes_of_unchecked(a),
+                table.get(a).unwrap().as_bytes()
+            );
+            assert_eq!(table.bytes_of_unchecked(b), b"");
+        }
+    }
+
+    #[test]
+    fn as_ref_returns_physical_buffer() {
+        fn payload<T: AsRef<[u8]>>(value: &T) -> &[u8] {
+            value.as_ref()
+        }
+
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("cat").unwrap();
+        let table = builder.build();
+
+        assert_eq!(payload(&table), b"cat\0");
+
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("cat").unwrap();
+        let aligned = builder.build_aligned::<8>().unwrap();
+        assert_eq!(payload(&aligned), b"cat\0\0\0\0\0");
+        assert_eq!(aligned.offsets(), &[0, 4]);
+    }
+
+    #[test]
+    fn eq_strings_checks_length_and_content() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("a").unwrap();
+        builder.try_push("").unwrap();
+        let table = builder.build();
+
+        assert!(table.eq_strings(["a", ""]));
+        assert!(table.eq_strings(alloc::vec![alloc::string::String::from("a"), "".into()]));
+        assert!(!table.eq_strings(["a"]));
+        assert!(!table.eq_strings(["a", "", ""]));
+        assert!(!table.eq_strings(["b", ""]));
+        assert!(StringTable::empty().eq_strings(core::iter::empty::<&str>()));
+    }
+
+    #[test]
+    fn write_str_to_appends_to_writer() {
+        let mut builder = StringTableBuilder::new();
+        let a = builder.try_push("foo").unwrap();
+        let b = builder.try_push("bar").unwrap();
+        let table = builder.build();
+
+        let mut out = alloc::string::String::from("> ");
+        assert_eq!(table.write_str_to(a, &mut out), Some(Ok(())));
+        assert_eq!(table.write_str_to(b, &mut out), Some(Ok(())));
+        assert_eq!(table.write_str_to(StringId::new(2), &mut out), None);
+        assert_eq!(out, "> foobar");
+    }
+
+    #[test]
+    fn get_pair_requires_both_ids() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let key = builder.try_push("key").unwrap();
+        let value = builder.try_push("value").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get_pair(key, value), Some(("key", "value")));
+        assert_eq!(table.get_pair(value, value), Some(("value", "value")));
+        assert_eq!(table.get_pair(key, StringId::new(2)), None);
+        assert_eq!(table.get_pair(StringId::new(2), key), None);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let empty = StringTable::empty();
+        assert_eq!((empty.first(), empty.last()), (None, None));
+
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("only").unwrap();
+        let single = builder.build();
+        assert_eq!(
+            (single.first(), single.last()),
+            (Some("only"), Some("only"))
+        );
+
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["head", "middle", "tail"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+        assert_eq!(table.first(), Some("head"));
+        assert_eq!(table.last(), Some("tail"));
+    }
+
+    #[test]
+    fn get_by_index_matches_get() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("cat").unwrap();
+        builder.try_push("dog").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get_by_index(1), Some("dog"));
+        assert_eq!(table.get_by_index(1), table.get(StringId::new(1)));
+        assert_eq!(table.get_by_index(2), None);
+        assert_eq!(table.get_by_index(usize::MAX), None);
+    }
+
+    #[test]
+    fn get_or_falls_back_for_invalid_id() {
+        let mut builder = StringTableBuilder::new();
+        let id = builder.try_push("cat").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get_or(id, "?"), "cat");
+        assert_eq!(table.get_or(StringId::new(1), "?"), "?");
+        assert_eq!(table.get_or_empty(id), "cat");
+        assert_eq!(table.get_or_empty(StringId::new(1)), "");
+    }
+
+    #[test]
+    fn get_returns_none_for_invalid_id() {
+        let table = StringTable::empty();
+        assert_eq!(table.get(StringId::new(0)), None);
+    }
+}
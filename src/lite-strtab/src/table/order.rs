@@ -0,0 +1,330 @@
+//! Entry orderings derived from string contents.
+
+use core::ops::Range;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable};
+
+/// Marks a missing neighbour in the sorted linked list.
+const NONE: usize = usize::MAX;
+
+/// Returns the length in bytes of the longest common prefix of `a` and `b`.
+#[inline]
+pub(crate) fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns `true` when the strings are in ascending byte order.
+    ///
+    /// Adjacent equal strings are allowed, and tables with fewer than two
+    /// strings are sorted. Scans [`Self::pairs`] and stops at the first
+    /// out-of-order pair; see [`Self::debug_assert_sorted`] for a check that
+    /// reports it.
+    #[inline]
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.pairs().all(|(previous, next)| previous <= next)
+    }
+
+    /// Panics in debug builds if the strings are not in ascending byte order.
+    ///
+    /// The panic message names the first out-of-order pair. Adjacent equal
+    /// strings are allowed. In release builds this does nothing, so code that
+    /// relies on a sorted table can check its precondition for free.
+    #[inline]
+    #[track_caller]
+    pub fn debug_assert_sorted(&self) {
+        #[cfg(debug_assertions)]
+        {
+            let mut pairs = self.pairs().enumerate();
+            if let Some((index, (previous, next))) = pairs.find(|(_, (a, b))| a > b) {
+                panic!(
+                    "string table is not sorted: entry {index} ({previous:?}) > entry {} ({next:?})",
+                    index + 1
+                );
+            }
+        }
+    }
+
+    /// Returns an iterator over each pair of adjacent strings, in ID order.
+    ///
+    /// Yields `len() - 1` pairs, and nothing for tables with fewer than two
+    /// strings. Each step reads one window of three offsets rather than
+    /// looking both strings up, which suits computing shared prefixes of
+    /// sorted entries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// for value in ["a/b", "a/c", "b"] {
+    ///     builder.try_push(value).unwrap();
+    /// }
+    /// let table = builder.build();
+    ///
+    /// assert!(table.pairs().eq([("a/b", "a/c"), ("a/c", "b")]));
+    /// ```
+    pub fn pairs(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.offsets.windows(3).map(|window| {
+            let [start, middle, end] = [window[0], window[1], window[2]].map(Offset::to_usize);
+            // Const generic: default (`false`) folds the subtractions away.
+            let padding = usize::from(NULL_PADDED);
+            let first = &self.bytes[start..middle - padding];
+            let second = &self.bytes[middle..end - padding];
+            // SAFETY: Table invariants guarantee every string is valid UTF-8.
+            unsafe {
+                (
+                    core::str::from_utf8_unchecked(first),
+                    core::str::from_utf8_unchecked(second),
+                )
+            }
+        })
+    }
+
+    /// Returns the range of indices of all strings equal to `value`.
+    ///
+    /// The table must be sorted in ascending byte order (see
+    /// [`Self::debug_assert_sorted`]); otherwise the result is unspecified.
+    /// If `value` is absent, the range is empty and starts at the index where
+    /// it would be inserted. Uses two binary searches, like C++'s
+    /// `std::equal_range`.
+    ///
+    /// The range holds `usize` indices because its end may equal
+    /// [`Self::len`], which need not fit in `I`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// for value in ["a", "b", "b", "d"] {
+    ///     builder.try_push(value).unwrap();
+    /// }
+    /// let table = builder.build();
+    ///
+    /// assert_eq!(table.equal_range("b"), 1..3);
+    /// assert_eq!(table.equal_range("c"), 3..3);
+    /// ```
+    pub fn equal_range(&self, value: &str) -> Range<usize> {
+        let start = self.partition_point(|item| item < value);
+        let end = start + self.partition_point_from(start, |item| item <= value);
+        start..end
+    }
+
+    /// Returns the number of leading strings, starting at `from`, for which
+    /// `pred` holds, assuming `pred` is `true` for a prefix of them.
+    fn partition_point_from<P: FnMut(&str) -> bool>(&self, from: usize, mut pred: P) -> usize {
+        let (mut low, mut high) = (from, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            // SAFETY: `from <= low <= mid < high <= len`.
+            if pred(unsafe { self.get_unchecked(Self::id_at(mid)) }) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low - from
+    }
+
+    /// Returns the index of the first string for which `pred` is `false`,
+    /// assuming it is `true` for a prefix of the table.
+    #[inline]
+    fn partition_point<P: FnMut(&str) -> bool>(&self, pred: P) -> usize {
+        self.partition_point_from(0, pred)
+    }
+
+    /// Returns an ID ordering in which each entry greedily shares the longest
+    /// possible byte prefix with the entry placed before it.
+    ///
+    /// Rebuilding a table in this order tends to make front coding compress
+    /// better. This is a heuristic: it starts from the lexicographically
+    /// smallest string and never revisits a choice, so the total shared prefix
+    /// length is not guaranteed to be optimal.
+    ///
+    /// Runs in `O(n log n)` string comparisons: among the remaining entries,
+    /// the best match for a string is always one of its neighbours in sorted
+    /// order.
+    pub fn greedy_prefix_order(&self) -> Box<[StringId<I>], A> {
+        let len = self.len();
+        let mut sorted = Vec::with_capacity_in(len, Global);
+        sorted.extend(self.iter().enumerate().map(|(index, value)| (value, index)));
+        sorted.sort_unstable();
+
+        // Doubly linked list over `sorted` positions of entries not yet placed.
+        let mut prev = Vec::with_capacity_in(len, Global);
+        let mut next = Vec::with_capacity_in(len, Global);
+        for position in 0..len {
+            prev.push(position.checked_sub(1).unwrap_or(NONE));
+            next.push(if position + 1 < len {
+                position + 1
+            } else {
+                NONE
+            });
+        }
+
+        let mut order = Vec::with_capacity_in(len, self.allocator().clone());
+        let mut current = if len == 0 { NONE } else { 0 };
+        while current != NONE {
+            let (value, index) = sorted[current];
+            order.push(Self::id_at(index));
+
+            let (before, after) = (prev[current], next[current]);
+            if before != NONE {
+                next[before] = after;
+            }
+            if after != NONE {
+                prev[after] = before;
+            }
+
+            let shared = |position: usize| {
+                common_prefix_len(value.as_bytes(), sorted[position].0.as_bytes())
+            };
+            current = match (before, after) {
+                (NONE, after) => after,
+                (before, NONE) => before,
+                // Ties go forward, which keeps runs in sorted order.
+                (before, after) if shared(after) >= shared(before) => after,
+                (before, _) => before,
+            };
+        }
+        order.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::common_prefix_len;
+    use crate::{StringTable, StringTableBuilder};
+
+    fn adjacent_prefix_total<'a>(values: impl Iterator<Item = &'a str>) -> usize {
+        let values: Vec<_> = values.collect();
+        values
+            .windows(2)
+            .map(|pair| common_prefix_len(pair[0].as_bytes(), pair[1].as_bytes()))
+            .sum()
+    }
+
+    #[test]
+    fn greedy_prefix_order_beats_interleaved_insertion_order() {
+        let mut builder = StringTableBuilder::new();
+        for value in [
+            "textures/wall.dds",
+            "sound/bgm.awb",
+            "textures/water.dds",
+            "model/player.gmd",
+            "sound/bgm_boss.awb",
+            "model/player_alt.gmd",
+            "textures/wall_n.dds",
+        ] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let order = table.greedy_prefix_order();
+        let mut seen: Vec<_> = order.iter().map(|id| id.into_usize()).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..table.len()).collect::<Vec<_>>());
+
+        let reordered = adjacent_prefix_total(order.iter().map(|&id| table.get(id).unwrap()));
+        let original = adjacent_prefix_total(table.iter());
+        assert!(reordered >= original);
+        assert!(reordered > 0);
+        assert_eq!(original, 0);
+    }
+
+    #[test]
+    fn pairs_yields_adjacent_strings() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["x", "", "yz"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let pairs = table.pairs();
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.eq([("x", ""), ("", "yz")]));
+
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("only").unwrap();
+        assert_eq!(builder.build().pairs().len(), 0);
+        assert_eq!(StringTable::empty().pairs().len(), 0);
+    }
+
+    #[test]
+    fn is_sorted_checks_adjacent_pairs() {
+        let table = |values: &[&str]| {
+            let mut builder = StringTableBuilder::new();
+            for value in values {
+                builder.try_push(value).unwrap();
+            }
+            builder.build()
+        };
+
+        assert!(table(&[]).is_sorted());
+        assert!(table(&["z"]).is_sorted());
+        assert!(table(&["", "a", "a", "ab", "b"]).is_sorted());
+        assert!(!table(&["a", "b", "a"]).is_sorted());
+        assert!(!table(&["b", "a"]).is_sorted());
+    }
+
+    #[test]
+    fn debug_assert_sorted_accepts_sorted_tables() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["", "a", "a", "ab", "b"] {
+            builder.try_push(value).unwrap();
+        }
+        builder.build().debug_assert_sorted();
+        StringTableBuilder::new().build().debug_assert_sorted();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "entry 1 (\"b\") > entry 2 (\"a\")")]
+    fn debug_assert_sorted_panics_on_unsorted_table() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["a", "b", "a"] {
+            builder.try_push(value).unwrap();
+        }
+        builder.build().debug_assert_sorted();
+    }
+
+    #[test]
+    fn equal_range_spans_duplicates() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["", "apple", "pear", "pear", "pear", "plum"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        assert_eq!(table.equal_range("pear"), 2..5);
+        assert_eq!(table.equal_range(""), 0..1);
+        assert_eq!(table.equal_range("plum"), 5..6);
+        assert_eq!(table.equal_range("banana"), 2..2);
+        assert_eq!(table.equal_range("zebra"), 6..6);
+        assert_eq!(StringTableBuilder::new().build().equal_range("x"), 0..0);
+    }
+
+    #[test]
+    fn greedy_prefix_order_handles_small_tables() {
+        let empty = StringTableBuilder::new().build();
+        assert!(empty.greedy_prefix_order().is_empty());
+
+        let mut builder = StringTableBuilder::new();
+        let only = builder.try_push("only").unwrap();
+        assert_eq!(&*builder.build().greedy_prefix_order(), [only]);
+    }
+}
@@ -0,0 +1,190 @@
+//! Tables stored in a single allocation.
+
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::str;
+
+use crate::allocator::*;
+use crate::{Offset, StringId, StringIndex, StringTable, TableHeader};
+
+/// Immutable string table whose header, offsets and string bytes share one
+/// allocation.
+///
+/// The buffer uses the [`StringTable::to_bytes`] format: a [`TableHeader`],
+/// then `len() + 1` little-endian offsets of `size_of::<O>()` bytes each,
+/// then the string data. Lookups find both regions from the string count,
+/// so a table costs one allocation instead of two, and
+/// [`Self::as_packed_bytes`] can be written out or mapped as-is and read back
+/// with [`StringTable::from_bytes_in`].
+///
+/// Produced by [`crate::StringTableBuilder::build_packed`] and
+/// [`StringTable::to_packed`]. Offsets are decoded on every lookup, so
+/// [`StringTable`] remains the faster choice when allocation count does not
+/// matter.
+///
+/// # Example
+///
+/// ```rust
+/// use lite_strtab::{StringTable, StringTableBuilder};
+///
+/// let mut builder = StringTableBuilder::new();
+/// let id = builder.try_push("hello").unwrap();
+/// builder.try_push("world").unwrap();
+/// let packed = builder.build_packed();
+///
+/// assert_eq!(packed.get(id), Some("hello"));
+/// let table = StringTable::from_bytes(packed.as_packed_bytes()).unwrap();
+/// assert_eq!(table, ["hello", "world"][..]);
+/// ```
+pub struct PackedStringTable<
+    O = u32,
+    I = u16,
+    const NULL_PADDED: bool = false,
+    A: Allocator + Clone = Global,
+    const TERMINATOR: u8 = 0,
+> where
+    O: Offset,
+    I: StringIndex,
+{
+    buffer: Box<[u8], A>,
+    len: usize,
+    _types: PhantomData<(O, I)>,
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Copies the table into a [`PackedStringTable`] using its allocator.
+    ///
+    /// Alignment padding from
+    /// [`StringTableBuilder::build_aligned`](crate::StringTableBuilder::build_aligned)
+    /// is dropped.
+    #[inline]
+    #[must_use]
+    pub fn to_packed(&self) -> PackedStringTable<O, I, NULL_PADDED, A, TERMINATOR> {
+        PackedStringTable::from_encoded(self.to_bytes(), self.len())
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > PackedStringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Wraps the [`StringTable::encode_in`] output for a table of `len`
+    /// strings.
+    #[inline]
+    pub(crate) fn from_encoded(buffer: Vec<u8, A>, len: usize) -> Self {
+        Self {
+            buffer: buffer.into_boxed_slice(),
+            len,
+            _types: PhantomData,
+        }
+    }
+
+    /// Number of strings in the table.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the table has no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the string for a given ID.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: StringId<I>) -> Option<&str> {
+        self.get_by_index(id.into_usize())
+    }
+
+    /// Returns the string at a raw index, without constructing a [`StringId`].
+    #[inline]
+    #[must_use]
+    pub fn get_by_index(&self, index: usize) -> Option<&str> {
+        (index < self.len).then(|| self.str_at(index))
+    }
+
+    /// Iterates over all strings in ID order.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + DoubleEndedIterator + '_ {
+        (0..self.len).map(|index| self.str_at(index))
+    }
+
+    /// Returns the whole buffer: header, offsets and string data.
+    #[inline]
+    #[must_use]
+    pub fn as_packed_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns string `index`, which must be below `len`.
+    #[inline]
+    fn str_at(&self, index: usize) -> &str {
+        let data = TableHeader::SIZE + (self.len + 1) * size_of::<O>();
+        let start = data + self.offset_at(index);
+        // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+        let end = (data + self.offset_at(index + 1)).saturating_sub(usize::from(NULL_PADDED));
+        // SAFETY: The buffer was encoded from a valid table, so this range is
+        // in bounds and valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.buffer.get_unchecked(start..end)) }
+    }
+
+    /// Decodes offset `index`, which must be at most `len`.
+    #[inline]
+    fn offset_at(&self, index: usize) -> usize {
+        let width = size_of::<O>();
+        let position = TableHeader::SIZE + index * width;
+        let mut raw = [0u8; 8];
+        raw[..width].copy_from_slice(&self.buffer[position..position + width]);
+        // The offset was encoded from a `usize`, so it fits.
+        u64::from_le_bytes(raw) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::Global;
+    use crate::{StringId, StringTable, StringTableBuilder};
+
+    #[test]
+    fn packed_lookups_match_table() {
+        let mut builder = StringTableBuilder::<u16, u8, true>::new_in(Global);
+        for value in ["one", "", "three"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+        let packed = table.to_packed();
+
+        assert_eq!(packed.len(), 3);
+        for (index, value) in table.iter().enumerate() {
+            assert_eq!(packed.get_by_index(index), Some(value));
+        }
+        assert_eq!(packed.get(StringId::new(3)), None);
+        assert!(packed.iter().rev().eq(["three", "", "one"]));
+        assert_eq!(packed.as_packed_bytes(), &table.to_bytes()[..]);
+    }
+
+    #[test]
+    fn empty_packed_table_roundtrips() {
+        let packed = StringTableBuilder::new().build_packed();
+        assert!(packed.is_empty());
+        assert_eq!(packed.iter().next(), None);
+
+        let table = StringTable::from_bytes(packed.as_packed_bytes()).unwrap();
+        assert!(table.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+//! Byte ranges and raw offsets of stored strings.
+
+use core::ops::Range;
+use core::str;
+
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringId, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns the raw offset at `index` in [`Self::offsets`], or [`None`] if
+    /// `index > self.len()`.
+    ///
+    /// Index `self.len()` is the sentinel.
+    #[inline]
+    #[must_use]
+    pub fn raw_offset(&self, index: usize) -> Option<O> {
+        self.offsets.get(index).copied()
+    }
+
+    /// Returns the final sentinel offset: the end of the last string.
+    ///
+    /// This excludes any alignment padding in [`Self::as_bytes`].
+    #[inline]
+    #[must_use]
+    pub fn sentinel(&self) -> O {
+        // SAFETY: The offset table always holds at least the sentinel.
+        unsafe { *self.offsets.get_unchecked(self.offsets.len() - 1) }
+    }
+
+    /// Returns pointers to and lengths of the byte buffer and offset table,
+    /// for handing the table to foreign code without copying.
+    ///
+    /// Returns `(bytes, bytes_len, offsets, offsets_len)`. The offset table
+    /// includes the final sentinel, so `offsets_len == self.len() + 1`. Both
+    /// pointers are valid for reads for as long as `self` is borrowed and must
+    /// not be written through. Offsets are stored in native byte order; see
+    /// [`crate::TableHeader`] for describing the layout to a consumer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("hi").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let (bytes, bytes_len, offsets, offsets_len) = table.as_raw_parts();
+    /// assert_eq!((bytes_len, offsets_len), (2, 2));
+    /// // SAFETY: `table` is still borrowed, and `offsets_len` is in bounds.
+    /// assert_eq!(unsafe { *offsets.add(1) }, 2);
+    /// assert_eq!(bytes, table.as_bytes().as_ptr());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn as_raw_parts(&self) -> (*const u8, usize, *const O, usize) {
+        (
+            self.bytes.as_ptr(),
+            self.bytes.len(),
+            self.offsets.as_ptr(),
+            self.offsets.len(),
+        )
+    }
+
+    /// Returns the offset table, including the final sentinel, as raw bytes.
+    ///
+    /// A zero-copy cast of [`Self::offsets`]. Offsets are in host byte order,
+    /// so the bytes are only meaningful to a reader on a machine with the
+    /// same endianness; use [`Self::to_bytes`] for a portable encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{Global, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::<u16, u16>::new_in(Global);
+    /// builder.try_push("hi").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let bytes = table.offsets_as_bytes();
+    /// assert_eq!(bytes.len(), 4);
+    /// assert_eq!(u16::from_ne_bytes([bytes[2], bytes[3]]), 2);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    #[must_use]
+    pub fn offsets_as_bytes(&self) -> &[u8]
+    where
+        O: bytemuck::Pod,
+    {
+        bytemuck::cast_slice(&self.offsets)
+    }
+
+    /// Returns the offset table converted to [`i32`], including the final sentinel.
+    ///
+    /// Columnar formats such as Apache Arrow and Parquet mandate signed 32-bit
+    /// offsets. Returns [`Error::TooManyBytesForOffsetType`] if any offset
+    /// exceeds [`i32::MAX`].
+    pub fn offsets_i32(&self) -> Result<Vec<i32, A>> {
+        // Offsets are non-decreasing, so the sentinel is the largest value.
+        let sentinel = self.offsets[self.offsets.len() - 1].to_usize();
+        if i32::try_from(sentinel).is_err() {
+            return Err(Error::TooManyBytesForOffsetType {
+                bytes: sentinel,
+                offset_type: "i32",
+            });
+        }
+
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), self.allocator().clone());
+        // Cannot truncate: every offset is at most `sentinel <= i32::MAX`.
+        offsets.extend(self.offsets.iter().map(|offset| offset.to_usize() as i32));
+        Ok(offsets)
+    }
+
+    /// Returns the byte range for a given ID.
+    #[inline]
+    #[must_use]
+    pub fn byte_range(&self, id: StringId<I>) -> Option<Range<usize>> {
+        let index = id.into_usize();
+        // Failure (None) is unlikely; users typically provide valid indices.
+        // Since likely/unlikely isn't stable, we structure this so the
+        // success path falls through without jumps, improving pipelining.
+        if index < self.len() {
+            // SAFETY: Bounds check above ensures `index` and `index + 1` are valid.
+            let start = unsafe { self.offsets.get_unchecked(index) }.to_usize();
+            let end = unsafe { self.offsets.get_unchecked(index + 1) }.to_usize();
+            // Const generic: default (`false`) folds `saturating_sub(0)` to `end`.
+            let logical_end = end.saturating_sub(usize::from(NULL_PADDED));
+            debug_assert!(logical_end >= start);
+
+            Some(start..logical_end)
+        } else {
+            None
+        }
+    }
+
+    /// Returns each string's byte range in ID order.
+    ///
+    /// Ranges index into [`Self::as_bytes`] and, like [`Self::byte_range`],
+    /// exclude the NUL terminator in null-padded mode. Prefer this over
+    /// pairing up [`Self::offsets`] by hand, which includes the sentinel.
+    #[inline]
+    #[must_use]
+    pub fn ranges(&self) -> impl ExactSizeIterator<Item = Range<usize>> + '_ {
+        self.offsets.windows(2).map(|pair| {
+            let start = pair[0].to_usize();
+            // Const generic: default (`false`) folds the subtraction away.
+            start..pair[1].to_usize() - usize::from(NULL_PADDED)
+        })
+    }
+
+    /// Returns each string's length in bytes, in ID order.
+    ///
+    /// Lengths come from subtracting adjacent offsets and exclude the NUL
+    /// terminator in null-padded mode; the byte buffer is never read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new_null_padded();
+    /// for value in ["a", "abc", ""] {
+    ///     builder.try_push(value).unwrap();
+    /// }
+    /// let table = builder.build();
+    ///
+    /// assert_eq!(table.lengths().max(), Some(3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lengths(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.offsets
+            .windows(2)
+            // Const generic: default (`false`) folds the subtraction away.
+            .map(|pair| pair[1].to_usize() - pair[0].to_usize() - usize::from(NULL_PADDED))
+    }
+
+    /// Returns every string's byte range in one allocation, indexed by ID.
+    ///
+    /// The materialized form of [`Self::ranges`], for callers that need
+    /// random access to ranges. Computing them is O(n) once; afterwards,
+    /// slicing [`Self::as_bytes`] with a stored range skips the offset
+    /// arithmetic done by every [`Self::get`].
+    #[doc(alias = "precompute_ranges", alias = "as_slice_of_ranges")]
+    #[must_use]
+    pub fn all_ranges(&self) -> Box<[Range<usize>], A> {
+        let mut ranges = Vec::with_capacity_in(self.len(), self.allocator().clone());
+        ranges.extend(self.ranges());
+        ranges.into_boxed_slice()
+    }
+
+    /// Returns the string and its byte range for a given ID.
+    ///
+    /// Equivalent to [`Self::get`] plus [`Self::byte_range`], but with a single
+    /// bounds check. Like [`Self::byte_range`], the range excludes the NUL
+    /// terminator in null-padded mode.
+    #[inline]
+    #[must_use]
+    pub fn get_with_range(&self, id: StringId<I>) -> Option<(&str, Range<usize>)> {
+        let range = self.byte_range(id)?;
+        // SAFETY: `byte_range` only returns in-bounds ranges, and table
+        // invariants guarantee they are valid UTF-8.
+        let bytes = unsafe { self.bytes.get_unchecked(range.clone()) };
+        Some((unsafe { str::from_utf8_unchecked(bytes) }, range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::{Global, Vec};
+    use crate::{Error, StringId, StringTable, StringTableBuilder};
+
+    #[test]
+    fn get_with_range_matches_get_and_byte_range() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("cat").unwrap();
+        let id = builder.try_push("dog").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.get_with_range(id), Some(("dog", 4..7)));
+        assert_eq!(table.get_with_range(StringId::new(2)), None);
+    }
+
+    #[test]
+    fn offsets_i32_converts_small_table() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("cat").unwrap();
+        builder.try_push("mouse").unwrap();
+        let table = builder.build();
+
+        assert_eq!(table.offsets_i32().unwrap().as_slice(), &[0, 3, 8]);
+    }
+
+    #[test]
+    fn offsets_i32_rejects_offsets_above_i32_max() {
+        let bytes = Vec::new_in(Global).into_boxed_slice();
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u64);
+        offsets.push(i32::MAX as u64 + 1);
+
+        let table =
+            StringTable::<u64, u16>::from_parts_unchecked(bytes, offsets.into_boxed_slice());
+        assert_eq!(
+            table.offsets_i32(),
+            Err(Error::TooManyBytesForOffsetType {
+                bytes: i32::MAX as usize + 1,
+                offset_type: "i32",
+            })
+        );
+    }
+
+    #[test]
+    fn ranges_match_byte_range() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let ids = [
+            builder.try_push("one").unwrap(),
+            builder.try_push("").unwrap(),
+            builder.try_push("three").unwrap(),
+        ];
+        let table = builder.build();
+
+        let ranges = table.ranges();
+        assert_eq!(ranges.len(), 3);
+        let expected = ids.map(|id| table.byte_range(id).unwrap());
+        assert!(ranges.eq(expected));
+        assert_eq!(table.ranges().nth(2), Some(5..10));
+        assert_eq!(StringTable::empty().ranges().len(), 0);
+    }
+
+    #[test]
+    fn lengths_match_strings() {
+        let mut builder = StringTableBuilder::<u8, u8, true>::new_in(Global);
+        for value in ["one", "", "caf\u{e9}"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        assert!(table.lengths().eq(table.iter().map(str::len)));
+        assert_eq!(table.lengths().len(), 3);
+        assert_eq!(StringTable::empty().lengths().len(), 0);
+    }
+
+    #[test]
+    fn all_ranges_matches_byte_range() {
+        let mut builder = StringTableBuilder::new();
+        let ids = [
+            builder.try_push("alpha").unwrap(),
+            builder.try_push("").unwrap(),
+            builder.try_push("gamma").unwrap(),
+        ];
+        let table = builder.build();
+
+        let ranges = table.all_ranges();
+        assert_eq!(ranges.len(), ids.len());
+        for id in ids {
+            assert_eq!(Some(ranges[id.into_usize()].clone()), table.byte_range(id));
+        }
+    }
+
+    #[test]
+    fn raw_offset_and_sentinel() {
+        let mut builder = StringTableBuilder::<u16>::new_in(Global);
+        builder.try_push("ab").unwrap();
+        builder.try_push("cde").unwrap();
+        let table = builder.build_aligned::<8>().unwrap();
+
+        assert_eq!(table.raw_offset(0), Some(0u16));
+        assert_eq!(table.raw_offset(1), Some(2));
+        assert_eq!(table.raw_offset(2), Some(5));
+        assert_eq!(table.raw_offset(3), None);
+        assert_eq!(table.sentinel(), 5);
+        assert_eq!(table.as_bytes().len(), 8);
+        assert_eq!(StringTable::empty().sentinel(), 0);
+    }
+}
@@ -0,0 +1,367 @@
+//! Linear scans over stored strings.
+
+use hashbrown::HashMap;
+
+use super::zero_offset;
+use crate::allocator::*;
+use crate::{Offset, Result, StringId, StringIndex, StringTable, StringTableBuilder};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns an iterator over every string containing `needle`, with its ID.
+    ///
+    /// Each string is checked with [`str::contains`], so a full pass costs
+    /// `O(n * m)` and allocates nothing. An empty `needle` matches every string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("sound/bgm.awb").unwrap();
+    /// let se = builder.try_push("sound/se.awb").unwrap();
+    /// builder.try_push("model/se.gmd").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let mut matches = table.iter_containing("se.awb");
+    /// assert_eq!(matches.next(), Some((se, "sound/se.awb")));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter_containing<'a>(
+        &'a self,
+        needle: &'a str,
+    ) -> impl Iterator<Item = (StringId<I>, &'a str)> + 'a {
+        self.iter()
+            .enumerate()
+            .filter(move |(_, value)| value.contains(needle))
+            .map(|(index, value)| (Self::id_at(index), value))
+    }
+
+    /// Returns the first string for which `f` returns `true`, with its ID.
+    ///
+    /// Stops at the first match.
+    #[inline]
+    pub fn find<F: FnMut(&str) -> bool>(&self, mut f: F) -> Option<(StringId<I>, &str)> {
+        self.iter()
+            .enumerate()
+            .find(|(_, value)| f(value))
+            .map(|(index, value)| (Self::id_at(index), value))
+    }
+
+    /// Returns the longest string with its ID, or [`None`] if the table is
+    /// empty.
+    ///
+    /// Lengths are read from the offsets alone, so no string bytes are
+    /// touched until the result is returned. Ties go to the earliest ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("ab").unwrap();
+    /// let longest = builder.try_push("abcd").unwrap();
+    /// builder.try_push("wxyz").unwrap();
+    /// let table = builder.build();
+    ///
+    /// assert_eq!(table.longest(), Some((longest, "abcd")));
+    /// ```
+    #[inline]
+    pub fn longest(&self) -> Option<(StringId<I>, &str)> {
+        self.find_by_len(|len, best| len > best)
+    }
+
+    /// Returns the shortest string with its ID, or [`None`] if the table is
+    /// empty.
+    ///
+    /// Like [`Self::longest`], this scans offsets only and prefers the
+    /// earliest ID on ties.
+    #[inline]
+    pub fn shortest(&self) -> Option<(StringId<I>, &str)> {
+        self.find_by_len(|len, best| len < best)
+    }
+
+    /// Returns the first string whose length beats every earlier one
+    /// according to `better(len, best_len)`.
+    fn find_by_len(&self, better: impl Fn(usize, usize) -> bool) -> Option<(StringId<I>, &str)> {
+        let mut lengths = self.lengths();
+        let mut best_len = lengths.next()?;
+        let mut best = 0;
+        for (index, len) in lengths.enumerate() {
+            if better(len, best_len) {
+                (best, best_len) = (index + 1, len);
+            }
+        }
+
+        let id = Self::id_at(best);
+        // SAFETY: `best` indexes one of the scanned strings, so it is below `len()`.
+        Some((id, unsafe { self.get_unchecked(id) }))
+    }
+
+    /// Clears `dest` and pushes every string for which `f` returns `true`.
+    ///
+    /// Reusing one `dest` across calls keeps its allocations, so repeated
+    /// filtering does not reallocate once capacity has grown. Call
+    /// [`StringTableBuilder::build`] on `dest` to get the filtered table;
+    /// matching strings receive new, dense IDs in their original order.
+    ///
+    /// Returns the same errors as [`StringTableBuilder::try_push`].
+    pub fn filter_into<F: FnMut(StringId<I>, &str) -> bool>(
+        &self,
+        mut f: F,
+        dest: &mut StringTableBuilder<O, I, NULL_PADDED, A, TERMINATOR>,
+    ) -> Result<()> {
+        dest.clear();
+        for (index, value) in self.iter().enumerate() {
+            if f(Self::id_at(index), value) {
+                dest.try_push(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a table of the strings for which `f` returns `true`, plus a
+    /// mask indexed by original ID that is `true` for every kept string.
+    ///
+    /// Kept strings receive new, dense IDs in their original order, so the
+    /// new ID of a kept string is the number of `true` entries before it in
+    /// the mask. Filtering only removes strings, so it cannot fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// for value in ["keep", "drop", "keep too"] {
+    ///     builder.try_push(value).unwrap();
+    /// }
+    /// let table = builder.build();
+    ///
+    /// let (kept, mask) = table.retain_mask(|_, value| value.starts_with("keep"));
+    /// assert!(kept.eq_strings(["keep", "keep too"]));
+    /// assert_eq!(mask, [true, false, true]);
+    /// ```
+    pub fn retain_mask<F: FnMut(StringId<I>, &str) -> bool>(
+        &self,
+        mut f: F,
+    ) -> (Self, alloc::vec::Vec<bool>) {
+        let mut mask = alloc::vec::Vec::with_capacity(self.len());
+        let mut bytes = Vec::with_capacity_in(self.bytes.len(), self.allocator().clone());
+        let mut offsets = Vec::with_capacity_in(self.offsets.len(), self.allocator().clone());
+        offsets.push(zero_offset::<O>());
+
+        for (index, value) in self.iter().enumerate() {
+            let keep = f(Self::id_at(index), value);
+            mask.push(keep);
+            if keep {
+                bytes.extend_from_slice(value.as_bytes());
+                if NULL_PADDED {
+                    bytes.push(TERMINATOR);
+                }
+                debug_assert!(O::try_from_usize(bytes.len()).is_some());
+                // SAFETY: The kept bytes are a subset of this table's, whose
+                // length fits in `O`.
+                offsets.push(unsafe { O::try_from_usize(bytes.len()).unwrap_unchecked() });
+            }
+        }
+
+        let table =
+            Self::from_parts_unchecked(bytes.into_boxed_slice(), offsets.into_boxed_slice());
+        debug_assert!(table.validate().is_ok());
+        (table, mask)
+    }
+
+    /// Returns a table keeping only the longest string per first segment,
+    /// plus a remap from old to new IDs.
+    ///
+    /// Strings are grouped by the text before the first `delim` (or the whole
+    /// string if it has none). Within a group the longest string survives,
+    /// with ties going to the earliest. Survivors keep their relative order
+    /// and are renumbered densely; `remap[old]` is the new ID of the survivor
+    /// of `old`'s group.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringTableBuilder;
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("api/v1").unwrap();
+    /// builder.try_push("api/v1/users").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let (longest, remap) = table.keep_longest_per_first_segment('/').unwrap();
+    /// assert!(longest.eq_strings(["api/v1/users"]));
+    /// assert_eq!(remap[0], remap[1]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn keep_longest_per_first_segment(
+        &self,
+        delim: char,
+    ) -> Result<(Self, Box<[StringId<I>], A>)> {
+        // Group -> (index of its longest string, that string's length, its
+        // index in the new table).
+        let mut groups: HashMap<&str, (usize, usize, usize)> = HashMap::new();
+        for (index, value) in self.iter().enumerate() {
+            groups
+                .entry(first_segment(value, delim))
+                .and_modify(|(best, best_len, _)| {
+                    if value.len() > *best_len {
+                        (*best, *best_len) = (index, value.len());
+                    }
+                })
+                .or_insert((index, value.len(), 0));
+        }
+
+        let allocator = self.allocator().clone();
+        let mut builder = StringTableBuilder::new_in(allocator.clone());
+        for (index, value) in self.iter().enumerate() {
+            if let Some((best, _, new_index)) = groups.get_mut(first_segment(value, delim)) {
+                if *best == index {
+                    *new_index = builder.len();
+                    builder.try_push(value)?;
+                }
+            }
+        }
+
+        let mut remap = Vec::with_capacity_in(self.len(), allocator);
+        remap.extend(self.iter().map(|value| {
+            Self::id_at(
+                groups
+                    .get(first_segment(value, delim))
+                    .map_or(0, |group| group.2),
+            )
+        }));
+        Ok((builder.build(), remap.into_boxed_slice()))
+    }
+}
+
+/// Returns the text before the first `delim`, or all of `value` without one.
+#[inline]
+fn first_segment(value: &str, delim: char) -> &str {
+    value.split_once(delim).map_or(value, |(head, _)| head)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{StringId, StringTable, StringTableBuilder};
+
+    #[test]
+    fn iter_containing_finds_all_matches() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let a = builder.try_push("cat").unwrap();
+        builder.try_push("dog").unwrap();
+        let c = builder.try_push("concatenate").unwrap();
+        let table = builder.build();
+
+        let found: Vec<_> = table.iter_containing("cat").collect();
+        assert_eq!(found, [(a, "cat"), (c, "concatenate")]);
+        assert_eq!(table.iter_containing("").count(), 3);
+        assert_eq!(table.iter_containing("bird").count(), 0);
+    }
+
+    #[test]
+    fn find_returns_first_match() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("model/a.gmd").unwrap();
+        let first = builder.try_push("sound/a.awb").unwrap();
+        builder.try_push("sound/b.awb").unwrap();
+        let table = builder.build();
+
+        assert_eq!(
+            table.find(|value| value.contains("sound/")),
+            Some((first, "sound/a.awb"))
+        );
+        assert_eq!(table.find(|value| value.contains("texture/")), None);
+    }
+
+    #[test]
+    fn longest_and_shortest_prefer_first_on_ties() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["bb", "a", "ccc", "d", "eee"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        assert_eq!(table.longest(), Some((StringId::new(2), "ccc")));
+        assert_eq!(table.shortest(), Some((StringId::new(1), "a")));
+        assert_eq!(StringTable::empty().longest(), None);
+        assert_eq!(StringTable::empty().shortest(), None);
+    }
+
+    #[test]
+    fn filter_into_reuses_builder() {
+        let mut builder = StringTableBuilder::new();
+        for value in ["apple", "banana", "avocado", "cherry"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let mut dest = StringTableBuilder::with_capacity(4, 32);
+        table
+            .filter_into(|_, value| value.starts_with('a'), &mut dest)
+            .unwrap();
+        assert_eq!(dest.len(), 2);
+        assert_eq!(dest.bytes_len(), "appleavocado".len());
+
+        table
+            .filter_into(|id, _| id.into_usize() % 2 == 1, &mut dest)
+            .unwrap();
+        assert_eq!(dest.bytes.capacity(), 32);
+        assert_eq!(dest.offsets.capacity(), 5);
+        let filtered = dest.build();
+        assert_eq!(filtered.iter().collect::<Vec<_>>(), ["banana", "cherry"]);
+    }
+
+    #[test]
+    fn retain_mask_reports_kept_ids() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["a", "", "bb", "ccc"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let (kept, mask) = table.retain_mask(|id, value| id.into_usize() == 1 || value.len() > 1);
+        assert_eq!(mask, [false, true, true, true]);
+        assert_eq!(kept.validate(), Ok(()));
+        assert_eq!(kept.as_bytes(), b"\0bb\0ccc\0");
+        assert_eq!(kept.offsets(), &[0u32, 1, 4, 8]);
+
+        let (none, mask) = table.retain_mask(|_, _| false);
+        assert!(none.is_empty());
+        assert_eq!(mask, [false; 4]);
+    }
+
+    #[test]
+    fn keep_longest_per_first_segment_on_paths() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in [
+            "sound/bgm.awb",
+            "model/a.gmd",
+            "sound/bgm_boss.awb",
+            "readme",
+            "model/b.gmd",
+            "sound/se.awb",
+        ] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        let (longest, remap) = table.keep_longest_per_first_segment('/').unwrap();
+        assert!(longest.eq_strings(["model/a.gmd", "sound/bgm_boss.awb", "readme"]));
+        let remap: Vec<u16> = remap.iter().map(|id| id.into_raw()).collect();
+        assert_eq!(remap, [1, 0, 1, 2, 0, 1]);
+    }
+}
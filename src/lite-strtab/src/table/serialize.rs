@@ -0,0 +1,582 @@
+//! Binary serialization with a fixed header.
+
+use core::mem::{size_of, size_of_val};
+
+use super::varint::{read_varint, write_varint};
+use super::zero_offset;
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringIndex, StringTable};
+
+/// Fixed-size header at the start of [`StringTable::to_bytes`] and
+/// [`StringTable::to_bytes_compressed`] output.
+///
+/// The layout is `#[repr(C)]` with no padding, so C code can declare a
+/// matching struct:
+///
+/// | Offset | Size | Field            | Contents                                         |
+/// | ------ | ---- | ---------------- | ------------------------------------------------ |
+/// | 0      | 4    | `magic`          | [`Self::MAGIC`] or [`Self::COMPRESSED_MAGIC`]    |
+/// | 4      | 1    | `version`        | Format version, currently [`Self::VERSION`]      |
+/// | 5      | 1    | `offset_width`   | Offset width in bytes (`size_of::<O>()`)         |
+/// | 6      | 1    | `id_width`       | ID width in bytes (`size_of::<I>()`)             |
+/// | 7      | 1    | `null_padded`    | `1` if null-padded, else `0`                     |
+/// | 8      | 1    | `terminator`     | Terminator byte if null-padded, else `0`         |
+/// | 9      | 7    | `reserved`       | Zero                                             |
+/// | 16     | 8    | `string_count`   | Number of strings                                |
+/// | 24     | 8    | `byte_len`       | Byte length of the string data                   |
+///
+/// Multi-byte fields are little-endian in the serialized bytes. The struct
+/// holds them in native byte order, so use [`Self::from_bytes`] and
+/// [`Self::to_bytes`] rather than reinterpreting memory on big-endian hosts.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableHeader {
+    /// Format identifier.
+    pub magic: [u8; 4],
+    /// Format version.
+    pub version: u8,
+    /// Size of the offset type in bytes.
+    pub offset_width: u8,
+    /// Size of the ID type in bytes.
+    pub id_width: u8,
+    /// `1` if strings are NUL-terminated, else `0`.
+    pub null_padded: u8,
+    /// Byte ending each string in null-padded tables (`TERMINATOR`), else
+    /// `0`.
+    pub terminator: u8,
+    /// Reserved for future use; must be zero.
+    pub reserved: [u8; 7],
+    /// Number of strings in the table.
+    pub string_count: u64,
+    /// Byte length of the string data, excluding alignment padding.
+    pub byte_len: u64,
+}
+
+impl TableHeader {
+    /// Size of the header in bytes.
+    pub const SIZE: usize = 32;
+    /// Magic for the format with absolute offsets.
+    pub const MAGIC: [u8; 4] = *b"LSTB";
+    /// Magic for the format with varint-encoded lengths.
+    pub const COMPRESSED_MAGIC: [u8; 4] = *b"LSTD";
+    /// Current format version.
+    pub const VERSION: u8 = 1;
+
+    /// Encodes the header, writing multi-byte fields as little-endian.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[..4].copy_from_slice(&self.magic);
+        out[4..8].copy_from_slice(&[
+            self.version,
+            self.offset_width,
+            self.id_width,
+            self.null_padded,
+        ]);
+        out[8] = self.terminator;
+        out[9..16].copy_from_slice(&self.reserved);
+        out[16..24].copy_from_slice(&self.string_count.to_le_bytes());
+        out[24..32].copy_from_slice(&self.byte_len.to_le_bytes());
+        out
+    }
+
+    /// Decodes a header written by [`Self::to_bytes`].
+    ///
+    /// No field is checked; the table decoders do that.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let read_u64 = |position: usize| {
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(&bytes[position..position + 8]);
+            u64::from_le_bytes(raw)
+        };
+        let mut magic = [0u8; 4];
+        magic.copy_from_slice(&bytes[..4]);
+        let mut reserved = [0u8; 7];
+        reserved.copy_from_slice(&bytes[9..16]);
+
+        Self {
+            magic,
+            version: bytes[4],
+            offset_width: bytes[5],
+            id_width: bytes[6],
+            null_padded: bytes[7],
+            terminator: bytes[8],
+            reserved,
+            string_count: read_u64(16),
+            byte_len: read_u64(24),
+        }
+    }
+}
+
+// The documented layout has no padding.
+const _: () = assert!(size_of::<TableHeader>() == TableHeader::SIZE);
+
+impl StringTable<u32, u16, false, Global> {
+    /// Decodes the output of [`StringTable::to_bytes`] using the global
+    /// allocator.
+    ///
+    /// See [`StringTable::from_bytes_in`] for errors.
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        Self::from_bytes_in(data, Global)
+    }
+
+    /// Decodes the output of [`StringTable::to_bytes_compressed`] using the
+    /// global allocator.
+    ///
+    /// See [`StringTable::from_bytes_compressed_in`] for errors.
+    #[inline]
+    pub fn from_bytes_compressed(data: &[u8]) -> Result<Self> {
+        Self::from_bytes_compressed_in(data, Global)
+    }
+}
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Serializes the table with absolute offsets.
+    ///
+    /// The output is independent of host endianness.
+    ///
+    /// # Format
+    ///
+    /// Both formats start with a [`TableHeader`] whose magic selects the
+    /// encoding. [`TableHeader::MAGIC`] is followed by `count + 1`
+    /// little-endian offsets of the offset width.
+    /// [`TableHeader::COMPRESSED_MAGIC`] is instead followed by each string's
+    /// stored length (including its NUL in null-padded mode) as an LEB128
+    /// varint. Both end with the string data. Alignment padding from
+    /// [`crate::StringTableBuilder::build_aligned`] is not serialized.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8, A> {
+        self.to_bytes_in(self.allocator().clone())
+    }
+
+    /// Like [`Self::to_bytes`], but allocates the output in `allocator`.
+    pub fn to_bytes_in<A2: Allocator>(&self, allocator: A2) -> Vec<u8, A2> {
+        Self::encode_in(&self.bytes, &self.offsets, allocator)
+    }
+
+    /// Encodes `bytes` and `offsets` in the [`Self::to_bytes`] format.
+    ///
+    /// `offsets` must satisfy the table invariants for `bytes`; bytes past
+    /// the sentinel are left out.
+    pub(crate) fn encode_in<A2: Allocator>(
+        bytes: &[u8],
+        offsets: &[O],
+        allocator: A2,
+    ) -> Vec<u8, A2> {
+        let width = size_of::<O>();
+        let data_len = offsets.last().map_or(0, |sentinel| sentinel.to_usize());
+        let mut out = Vec::with_capacity_in(
+            TableHeader::SIZE + size_of_val(offsets) + data_len,
+            allocator,
+        );
+
+        let strings = offsets.len().saturating_sub(1);
+        out.extend_from_slice(&Self::header(TableHeader::MAGIC, strings, data_len).to_bytes());
+        for offset in offsets {
+            out.extend_from_slice(&(offset.to_usize() as u64).to_le_bytes()[..width]);
+        }
+        out.extend_from_slice(&bytes[..data_len]);
+        out
+    }
+
+    /// Serializes the table with each string's length stored as a varint
+    /// instead of absolute offsets.
+    ///
+    /// Short strings need a single length byte, so the offset table typically
+    /// shrinks to a quarter of its `u32` size. Decoding rebuilds the offsets
+    /// with a prefix sum, which is slower than [`Self::from_bytes_in`]. See
+    /// [`Self::to_bytes`] for the format.
+    pub fn to_bytes_compressed(&self) -> Vec<u8, A> {
+        let data_len = self.data_len();
+        let mut out = Vec::with_capacity_in(
+            TableHeader::SIZE + self.len() + data_len,
+            self.allocator().clone(),
+        );
+
+        let header = Self::header(TableHeader::COMPRESSED_MAGIC, self.len(), data_len);
+        out.extend_from_slice(&header.to_bytes());
+        for pair in self.offsets.windows(2) {
+            write_varint(&mut out, pair[1].to_usize() - pair[0].to_usize());
+        }
+        out.extend_from_slice(&self.bytes[..data_len]);
+        out
+    }
+
+    /// Decodes the output of [`Self::to_bytes`] with a custom allocator.
+    ///
+    /// Returns [`Error::InvalidEncoding`] if the input is truncated, has
+    /// trailing bytes, or was written for different `O`, `I`, `NULL_PADDED`
+    /// or `TERMINATOR` parameters, and [`Error::InvalidTable`] if the decoded
+    /// parts fail validation.
+    pub fn from_bytes_in(data: &[u8], allocator: A) -> Result<Self> {
+        let (strings, data_len) = Self::read_header(data, TableHeader::MAGIC)?;
+        let width = size_of::<O>();
+        let position = TableHeader::SIZE;
+
+        let offsets_len = strings
+            .checked_add(1)
+            .and_then(|count| count.checked_mul(width))
+            .filter(|&len| len <= data.len() - position)
+            .ok_or(Error::InvalidEncoding {
+                position,
+                reason: "offset table extends past the end of the input",
+            })?;
+
+        let mut offsets = Vec::with_capacity_in(strings + 1, allocator.clone());
+        for (index, encoded) in data[position..position + offsets_len]
+            .chunks_exact(width)
+            .enumerate()
+        {
+            let mut raw = [0u8; 8];
+            raw[..width].copy_from_slice(encoded);
+            let offset = usize::try_from(u64::from_le_bytes(raw))
+                .ok()
+                .and_then(O::try_from_usize)
+                .ok_or(Error::InvalidEncoding {
+                    position: position + index * width,
+                    reason: "offset does not fit in the offset type",
+                })?;
+            offsets.push(offset);
+        }
+
+        Self::from_encoded_parts(data, position + offsets_len, data_len, offsets, allocator)
+    }
+
+    /// Decodes the output of [`Self::to_bytes_compressed`] with a custom
+    /// allocator.
+    ///
+    /// Returns the same errors as [`Self::from_bytes_in`].
+    pub fn from_bytes_compressed_in(data: &[u8], allocator: A) -> Result<Self> {
+        let (strings, data_len) = Self::read_header(data, TableHeader::COMPRESSED_MAGIC)?;
+        let mut position = TableHeader::SIZE;
+
+        // Every length takes at least one byte; check before allocating.
+        if strings > data.len() - position {
+            return Err(Error::InvalidEncoding {
+                position,
+                reason: "length table extends past the end of the input",
+            });
+        }
+
+        let mut offsets = Vec::with_capacity_in(strings + 1, allocator.clone());
+        offsets.push(zero_offset::<O>());
+        let mut end = 0usize;
+        for _ in 0..strings {
+            let length_position = position;
+            let length = read_varint(data, &mut position)?;
+            // Lengths are unsigned, so the prefix sum is non-decreasing as
+            // long as it does not overflow.
+            end = end
+                .checked_add(length)
+                .filter(|&end| end <= data_len)
+                .ok_or(Error::InvalidEncoding {
+                    position: length_position,
+                    reason: "string lengths exceed the byte length",
+                })?;
+            offsets.push(O::try_from_usize(end).ok_or(Error::InvalidEncoding {
+                position: length_position,
+                reason: "offset does not fit in the offset type",
+            })?);
+        }
+
+        Self::from_encoded_parts(data, position, data_len, offsets, allocator)
+    }
+
+    /// Byte length of the string data, excluding alignment padding.
+    #[inline]
+    fn data_len(&self) -> usize {
+        self.sentinel().to_usize()
+    }
+
+    /// Value of [`TableHeader::terminator`] for this table type.
+    #[inline]
+    fn header_terminator() -> u8 {
+        if NULL_PADDED {
+            TERMINATOR
+        } else {
+            0
+        }
+    }
+
+    /// Returns the header for a table of this type with `strings` strings
+    /// over `byte_len` data bytes, with the given `magic`.
+    fn header(magic: [u8; 4], strings: usize, byte_len: usize) -> TableHeader {
+        TableHeader {
+            magic,
+            version: TableHeader::VERSION,
+            offset_width: size_of::<O>() as u8,
+            id_width: size_of::<I>() as u8,
+            null_padded: u8::from(NULL_PADDED),
+            terminator: Self::header_terminator(),
+            reserved: [0; 7],
+            string_count: strings as u64,
+            byte_len: byte_len as u64,
+        }
+    }
+
+    /// Checks the header against `magic` and this table type, returning the
+    /// string count and byte length.
+    fn read_header(data: &[u8], magic: [u8; 4]) -> Result<(usize, usize)> {
+        let invalid = |position, reason| Error::InvalidEncoding { position, reason };
+        let header = data
+            .get(..TableHeader::SIZE)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(TableHeader::from_bytes)
+            .ok_or(invalid(0, "input is shorter than the header"))?;
+
+        if header.magic != magic {
+            return Err(invalid(0, "unrecognized magic"));
+        }
+        if header.version != TableHeader::VERSION {
+            return Err(invalid(4, "unsupported format version"));
+        }
+        if usize::from(header.offset_width) != size_of::<O>() {
+            return Err(invalid(5, "offset width does not match the offset type"));
+        }
+        if usize::from(header.id_width) != size_of::<I>() {
+            return Err(invalid(6, "ID width does not match the ID type"));
+        }
+        if header.null_padded != u8::from(NULL_PADDED) {
+            return Err(invalid(7, "null-padded flag does not match the table type"));
+        }
+        if header.terminator != Self::header_terminator() {
+            return Err(invalid(8, "terminator does not match the table type"));
+        }
+        if header.reserved != [0; 7] {
+            return Err(invalid(9, "reserved bytes are not zero"));
+        }
+
+        let to_usize = |value: u64, position| {
+            usize::try_from(value).map_err(|_| invalid(position, "length does not fit in usize"))
+        };
+        Ok((
+            to_usize(header.string_count, 16)?,
+            to_usize(header.byte_len, 24)?,
+        ))
+    }
+
+    /// Pairs decoded `offsets` with the string data at `position` and
+    /// validates the result.
+    fn from_encoded_parts(
+        data: &[u8],
+        position: usize,
+        data_len: usize,
+        offsets: Vec<O, A>,
+        allocator: A,
+    ) -> Result<Self> {
+        let rest = &data[position..];
+        if rest.len() != data_len {
+            return Err(Error::InvalidEncoding {
+                position,
+                reason: if rest.len() < data_len {
+                    "string data extends past the end of the input"
+                } else {
+                    "unexpected bytes after the string data"
+                },
+            });
+        }
+
+        let mut bytes = Vec::with_capacity_in(data_len, allocator);
+        bytes.extend_from_slice(rest);
+        let table =
+            StringTable::from_parts_unchecked(bytes.into_boxed_slice(), offsets.into_boxed_slice());
+        table.validate()?;
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::{offset_of, size_of};
+
+    use super::TableHeader;
+    use crate::allocator::Global;
+    use crate::{Error, StringTable, StringTableBuilder, ValidationError};
+
+    fn sample() -> StringTable {
+        let mut builder = StringTableBuilder::new();
+        for value in ["id", "", "name", "caf\u{e9}", "x"] {
+            builder.try_push(value).unwrap();
+        }
+        builder.build()
+    }
+
+    fn decode_error(data: &[u8]) -> Error {
+        match StringTable::from_bytes(data) {
+            Err(error) => error,
+            Ok(_) => panic!("invalid input decoded successfully"),
+        }
+    }
+
+    #[test]
+    fn to_bytes_roundtrip() {
+        let table = sample();
+        let encoded = table.to_bytes();
+        assert_eq!(&encoded[..4], b"LSTB");
+        assert_eq!(encoded.len(), 32 + 6 * 4 + table.as_bytes().len());
+
+        let decoded = StringTable::from_bytes(&encoded).unwrap();
+        assert_eq!(decoded.offsets(), table.offsets());
+        assert_eq!(decoded.as_bytes(), table.as_bytes());
+    }
+
+    #[test]
+    fn to_bytes_compressed_roundtrip_is_smaller() {
+        let table = sample();
+        let compressed = table.to_bytes_compressed();
+        assert_eq!(&compressed[..4], b"LSTD");
+        assert!(compressed.len() < table.to_bytes().len());
+
+        let decoded = StringTable::from_bytes_compressed(&compressed).unwrap();
+        assert_eq!(decoded.offsets(), table.offsets());
+        assert_eq!(decoded.as_bytes(), table.as_bytes());
+
+        assert!(matches!(
+            StringTable::from_bytes(&compressed),
+            Err(Error::InvalidEncoding {
+                reason: "unrecognized magic",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn roundtrip_null_padded_drops_alignment_padding() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        builder.try_push("ab").unwrap();
+        builder.try_push("").unwrap();
+        let table = builder.build_aligned::<16>().unwrap();
+
+        for encoded in [table.to_bytes(), table.to_bytes_compressed()] {
+            assert!(encoded.ends_with(b"ab\0\0"));
+        }
+        let decoded = StringTable::<u32, u16, true>::from_bytes_compressed_in(
+            &table.to_bytes_compressed(),
+            Global,
+        )
+        .unwrap();
+        assert_eq!(decoded.as_bytes(), b"ab\0\0");
+        assert!(decoded.iter().eq(["ab", ""]));
+    }
+
+    #[test]
+    fn from_bytes_rejects_mismatched_or_truncated_input() {
+        let encoded = sample().to_bytes();
+
+        assert!(matches!(
+            StringTable::<u16>::from_bytes_in(&encoded, Global),
+            Err(Error::InvalidEncoding { position: 5, .. })
+        ));
+        assert!(matches!(
+            StringTable::<u32, u16, true>::from_bytes_in(&encoded, Global),
+            Err(Error::InvalidEncoding { position: 7, .. })
+        ));
+        assert!(matches!(
+            decode_error(&encoded[..encoded.len() - 1]),
+            Error::InvalidEncoding {
+                reason: "string data extends past the end of the input",
+                ..
+            }
+        ));
+        assert!(matches!(
+            decode_error(&encoded[..20]),
+            Error::InvalidEncoding { position: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn roundtrip_checks_terminator() {
+        let mut builder = StringTableBuilder::<u32, u16, true, Global, 0x1F>::new_in(Global);
+        builder.try_push("a").unwrap();
+        builder.try_push("bc").unwrap();
+        let table = builder.build();
+
+        let encoded = table.to_bytes();
+        assert_eq!(encoded[8], 0x1F);
+        let Ok(decoded) =
+            StringTable::<u32, u16, true, Global, 0x1F>::from_bytes_in(&encoded, Global)
+        else {
+            panic!("roundtrip failed");
+        };
+        assert!(decoded.iter().eq(["a", "bc"]));
+        assert!(matches!(
+            StringTable::<u32, u16, true>::from_bytes_in(&encoded, Global),
+            Err(Error::InvalidEncoding { position: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn from_bytes_validates_decoded_table() {
+        let mut encoded = sample().to_bytes();
+        // Offsets are [0, 2, 2, 6, 11, 12]; make offset[2] exceed offset[3].
+        encoded[32 + 2 * 4] = 7;
+        assert!(matches!(
+            decode_error(&encoded),
+            Error::InvalidTable(ValidationError::OffsetsNotMonotonic { .. })
+        ));
+
+        let mut encoded = sample().to_bytes();
+        let last = encoded.len() - 1;
+        encoded[last] = 0xFF;
+        assert!(matches!(
+            decode_error(&encoded),
+            Error::InvalidTable(ValidationError::InvalidUtf8 { .. })
+        ));
+    }
+
+    #[test]
+    fn from_bytes_compressed_rejects_lengths_past_data() {
+        let mut encoded = sample().to_bytes_compressed();
+        // First length byte: "id" claims 100 bytes.
+        encoded[32] = 100;
+        assert!(matches!(
+            StringTable::from_bytes_compressed(&encoded),
+            Err(Error::InvalidEncoding {
+                reason: "string lengths exceed the byte length",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn table_header_matches_serialized_bytes() {
+        assert_eq!(size_of::<TableHeader>(), TableHeader::SIZE);
+        assert_eq!(offset_of!(TableHeader, magic), 0);
+        assert_eq!(offset_of!(TableHeader, version), 4);
+        assert_eq!(offset_of!(TableHeader, offset_width), 5);
+        assert_eq!(offset_of!(TableHeader, id_width), 6);
+        assert_eq!(offset_of!(TableHeader, null_padded), 7);
+        assert_eq!(offset_of!(TableHeader, terminator), 8);
+        assert_eq!(offset_of!(TableHeader, reserved), 9);
+        assert_eq!(offset_of!(TableHeader, string_count), 16);
+        assert_eq!(offset_of!(TableHeader, byte_len), 24);
+
+        let table = sample();
+        let encoded = table.to_bytes();
+        let mut raw = [0u8; TableHeader::SIZE];
+        raw.copy_from_slice(&encoded[..TableHeader::SIZE]);
+        let header = TableHeader::from_bytes(&raw);
+        assert_eq!(
+            header,
+            TableHeader {
+                magic: TableHeader::MAGIC,
+                version: TableHeader::VERSION,
+                offset_width: 4,
+                id_width: 2,
+                null_padded: 0,
+                terminator: 0,
+                reserved: [0; 7],
+                string_count: 5,
+                byte_len: table.as_bytes().len() as u64,
+            }
+        );
+        assert_eq!(header.to_bytes(), raw);
+    }
+}
@@ -0,0 +1,121 @@
+//! Splitting a table into independent tables.
+
+use core::ops::Range;
+
+use crate::allocator::*;
+use crate::{Error, Offset, Result, StringId, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Splits the table at `id` into two new tables.
+    ///
+    /// The first table holds strings `[0, id)` and the second `[id, len)`.
+    /// Both copy their bytes into fresh buffers with offsets rebased to start
+    /// at zero, so IDs in the second table are renumbered from 0: the string
+    /// at `id` becomes ID 0. Alignment padding past the sentinel is not
+    /// carried over.
+    ///
+    /// Returns [`Error::IdOutOfBounds`] if `id` is past [`Self::len`]. Splitting
+    /// at `len` yields a copy of the table and an empty table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{StringId, StringTableBuilder};
+    ///
+    /// let mut builder = StringTableBuilder::new();
+    /// builder.try_push("a").unwrap();
+    /// let b = builder.try_push("b").unwrap();
+    /// builder.try_push("c").unwrap();
+    /// let table = builder.build();
+    ///
+    /// let (head, tail) = table.split_at(b).unwrap();
+    /// assert!(head.eq_strings(["a"]));
+    /// assert_eq!(tail.get(StringId::new(0)), Some("b"));
+    /// assert_eq!(tail.len(), 2);
+    /// ```
+    pub fn split_at(&self, id: StringId<I>) -> Result<(Self, Self)> {
+        let index = id.into_usize();
+        let len = self.len();
+        if index > len {
+            return Err(Error::IdOutOfBounds { id: index, len });
+        }
+
+        Ok((self.copy_range(0..index), self.copy_range(index..len)))
+    }
+
+    /// Copies strings `range` into a new table with offsets rebased to zero.
+    fn copy_range(&self, range: Range<usize>) -> Self {
+        let allocator = self.allocator().clone();
+        let offsets = &self.offsets[range.start..=range.end];
+        let base = offsets[0].to_usize();
+        let end = offsets[offsets.len() - 1].to_usize();
+
+        let mut bytes = Vec::with_capacity_in(end - base, allocator.clone());
+        bytes.extend_from_slice(&self.bytes[base..end]);
+
+        let mut rebased = Vec::with_capacity_in(offsets.len(), allocator);
+        rebased.extend(offsets.iter().map(|offset| {
+            // SAFETY: `offset - base <= offset`, which already fits in `O`.
+            unsafe { O::try_from_usize(offset.to_usize() - base).unwrap_unchecked() }
+        }));
+
+        let table =
+            Self::from_parts_unchecked(bytes.into_boxed_slice(), rebased.into_boxed_slice());
+        debug_assert!(table.validate().is_ok());
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, StringId, StringTableBuilder};
+
+    #[test]
+    fn split_at_rebases_both_halves() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        for value in ["alpha", "", "gamma", "delta"] {
+            builder.try_push(value).unwrap();
+        }
+        let table = builder.build();
+
+        for index in 0..=table.len() {
+            let Ok((head, tail)) = table.split_at(StringId::new(index as u16)) else {
+                panic!("split at {index} failed");
+            };
+            assert!(head.iter().eq(table.iter().take(index)));
+            assert!(tail.iter().eq(table.iter().skip(index)));
+            assert_eq!(tail.offsets()[0], 0);
+            assert_eq!(head.validate(), Ok(()));
+            assert_eq!(tail.validate(), Ok(()));
+            assert_eq!(
+                head.as_bytes().len() + tail.as_bytes().len(),
+                table.as_bytes().len()
+            );
+        }
+
+        let Ok((_, tail)) = table.split_at(StringId::new(2)) else {
+            panic!();
+        };
+        assert_eq!(tail.as_bytes(), b"gamma\0delta\0");
+        assert_eq!(tail.get(StringId::new(1)), Some("delta"));
+    }
+
+    #[test]
+    fn split_at_rejects_id_past_len() {
+        let mut builder = StringTableBuilder::new();
+        builder.try_push("x").unwrap();
+        let table = builder.build();
+
+        let Err(error) = table.split_at(StringId::new(2)) else {
+            panic!("expected out-of-bounds error");
+        };
+        assert_eq!(error, Error::IdOutOfBounds { id: 2, len: 1 });
+    }
+}
@@ -0,0 +1,59 @@
+//! Unicode normalization on read.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use crate::{Allocator, Offset, StringId, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Returns the string for a given ID in Unicode Normalization Form C (NFC).
+    ///
+    /// Stored strings are borrowed when already in NFC, so only strings that
+    /// actually change are allocated.
+    pub fn get_nfc(&self, id: StringId<I>) -> Option<Cow<'_, str>> {
+        let value = self.get(id)?;
+        Some(if is_nfc(value) {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(value.nfc().collect::<String>())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn get_nfc_composes_decomposed_sequences() {
+        let mut builder = StringTableBuilder::new();
+        let decomposed = builder.try_push("cafe\u{301}").unwrap();
+        let table = builder.build();
+
+        let value = table.get_nfc(decomposed).unwrap();
+        assert!(matches!(value, Cow::Owned(_)));
+        assert_eq!(value, "caf\u{e9}");
+    }
+
+    #[test]
+    fn get_nfc_borrows_normalized_strings() {
+        let mut builder = StringTableBuilder::new_null_padded();
+        let normalized = builder.try_push("caf\u{e9}").unwrap();
+        let table = builder.build();
+
+        let value = table.get_nfc(normalized).unwrap();
+        assert!(matches!(value, Cow::Borrowed("caf\u{e9}")));
+        assert_eq!(table.get_nfc(StringId::new(1)), None);
+    }
+}
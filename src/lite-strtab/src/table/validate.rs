@@ -0,0 +1,545 @@
+//! Structural and UTF-8 checks for tables assembled from raw parts.
+
+use core::ops::Range;
+use core::str;
+
+use crate::allocator::*;
+use crate::error::{ValidationError, ValidationResult};
+use crate::{Offset, StringIndex, StringTable};
+
+impl<
+        O: Offset,
+        I: StringIndex,
+        const NULL_PADDED: bool,
+        A: Allocator + Clone,
+        const TERMINATOR: u8,
+    > StringTable<O, I, NULL_PADDED, A, TERMINATOR>
+{
+    /// Creates a table from a byte buffer and offset table without checking
+    /// them.
+    ///
+    /// `offsets` holds each string's start followed by a sentinel equal to the
+    /// end of the string data, as returned by [`Self::offsets`]. Use
+    /// [`Self::validate`] to check the parts up front, or
+    /// [`Self::iter_checked`] to check them lazily while reading.
+    ///
+    /// # Safety
+    ///
+    /// Until the parts are known to be valid, only [`Self::validate`],
+    /// [`Self::iter_checked`], [`Self::len`], [`Self::is_empty`],
+    /// [`Self::as_bytes`] and [`Self::offsets`] may be called. Every other
+    /// method assumes valid parts and may read out of bounds or produce
+    /// invalid `&str`s otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::{Global, StringTable, ValidationError};
+    ///
+    /// let bytes = allocator_api2::vec![in Global; b'o', b'k', 0xFF];
+    /// let offsets = allocator_api2::vec![in Global; 0u32, 2, 3];
+    /// // SAFETY: Only `iter_checked` is used on the unvalidated table.
+    /// let table: StringTable = unsafe {
+    ///     StringTable::from_raw_parts(bytes.into_boxed_slice(), offsets.into_boxed_slice())
+    /// };
+    ///
+    /// let mut iter = table.iter_checked();
+    /// assert_eq!(iter.next(), Some(Ok("ok")));
+    /// assert_eq!(iter.next(), Some(Err(ValidationError::InvalidUtf8 { index: 1 })));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub unsafe fn from_raw_parts(bytes: Box<[u8], A>, offsets: Box<[O], A>) -> Self {
+        Self::from_parts_unchecked(bytes, offsets)
+    }
+
+    /// Checks that the table's parts uphold every invariant.
+    ///
+    /// Tables from this crate's builders and decoders are always valid; this
+    /// is for tables created with [`Self::from_raw_parts`]. It checks the
+    /// whole table at once; see [`Self::iter_checked`] for a lazy variant.
+    ///
+    /// Equivalent to [`Self::validate_structure`] followed by
+    /// [`Self::validate_utf8`].
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.validate_structure()?;
+        self.validate_utf8()
+    }
+
+    /// Checks every invariant except that strings are valid UTF-8.
+    ///
+    /// Covers the offset and ID type limits, the sentinel, and that offsets
+    /// are in bounds and non-decreasing, plus terminators in null-padded mode.
+    /// Pair it with [`Self::validate_utf8`], or skip that when the bytes were
+    /// already checked upstream.
+    pub fn validate_structure(&self) -> Result<(), ValidationError> {
+        check_structure::<O, I, NULL_PADDED, TERMINATOR>(&self.bytes, self.offsets.len(), |index| {
+            self.offsets[index].to_usize()
+        })
+    }
+
+    /// Checks that every string is valid UTF-8, assuming
+    /// [`Self::validate_structure`] already passed.
+    ///
+    /// Unless `TERMINATOR` is non-ASCII, this validates the byte buffer in one
+    /// pass and then only checks that each offset falls on a character
+    /// boundary, which is cheaper than validating string by string. The
+    /// failing string is only located on error.
+    ///
+    /// On a table whose structure is invalid the result is unspecified and
+    /// this may panic.
+    pub fn validate_utf8(&self) -> Result<(), ValidationError> {
+        check_utf8::<NULL_PADDED, TERMINATOR>(&self.bytes, self.offsets.len(), |index| {
+            self.offsets[index].to_usize()
+        })
+    }
+
+    /// Returns an iterator that checks each string before yielding it.
+    ///
+    /// For every string, the offsets must be in bounds and non-decreasing,
+    /// null-padded strings must end with `TERMINATOR`, and the bytes must be
+    /// valid UTF-8. The first failing string yields an `Err`, after which the
+    /// iterator ends. An offset table without a sentinel yields
+    /// [`ValidationError::MissingSentinelOffset`].
+    ///
+    /// Unlike [`Self::validate`], work is spread over iteration and stops early
+    /// when the caller does. Whole-table properties, such as the sentinel
+    /// matching the byte length, are not checked.
+    pub fn iter_checked(&self) -> impl Iterator<Item = Result<&str, ValidationError>> + '_ {
+        let mut next = 0;
+        let mut done = false;
+        core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            if self.offsets.is_empty() {
+                done = true;
+                return Some(Err(ValidationError::MissingSentinelOffset));
+            }
+            if next == self.len() {
+                done = true;
+                return None;
+            }
+
+            let entry = self.checked_entry(next);
+            next += 1;
+            done = entry.is_err();
+            Some(entry)
+        })
+    }
+
+    /// Checks string `index`, which must be below [`Self::len`], without
+    /// assuming any table invariant.
+    fn checked_entry(&self, index: usize) -> ValidationResult<&str> {
+        check_entry::<NULL_PADDED, TERMINATOR>(&self.bytes, index, |index| {
+            self.offsets[index].to_usize()
+        })
+    }
+}
+
+/// Checks every invariant of a table whose `offsets_len` offsets are read
+/// through `offset_at`.
+///
+/// Shared with tables whose offsets are not stored as `O`, such as archived
+/// ones.
+#[cfg(feature = "rkyv")]
+pub(crate) fn check_parts<
+    O: Offset,
+    I: StringIndex,
+    const NULL_PADDED: bool,
+    const TERMINATOR: u8,
+>(
+    bytes: &[u8],
+    offsets_len: usize,
+    offset_at: impl Fn(usize) -> usize,
+) -> ValidationResult<()> {
+    check_structure::<O, I, NULL_PADDED, TERMINATOR>(bytes, offsets_len, &offset_at)?;
+    check_utf8::<NULL_PADDED, TERMINATOR>(bytes, offsets_len, offset_at)
+}
+
+/// Checks every invariant of a table except UTF-8 validity, reading its
+/// `offsets_len` offsets through `offset_at`.
+fn check_structure<O: Offset, I: StringIndex, const NULL_PADDED: bool, const TERMINATOR: u8>(
+    bytes: &[u8],
+    offsets_len: usize,
+    offset_at: impl Fn(usize) -> usize,
+) -> ValidationResult<()> {
+    let bytes_len = bytes.len();
+    if O::try_from_usize(bytes_len).is_none() {
+        return Err(ValidationError::TooManyBytesForOffsetType {
+            bytes: bytes_len,
+            offset_type: O::TYPE_NAME,
+        });
+    }
+
+    let strings = offsets_len.saturating_sub(1);
+    if strings > 0 && I::try_from_usize(strings - 1).is_none() {
+        return Err(ValidationError::TooManyStrings {
+            strings,
+            id_type: I::TYPE_NAME,
+        });
+    }
+
+    if offsets_len == 0 {
+        return Err(ValidationError::MissingSentinelOffset);
+    }
+
+    let found_last = offset_at(offsets_len - 1);
+    if found_last > bytes_len {
+        return Err(ValidationError::LastOffsetMismatch {
+            found: found_last,
+            expected: bytes_len,
+        });
+    }
+    // Bytes past the sentinel are only allowed as NUL alignment padding.
+    if let Some(padding) = bytes[found_last..].iter().position(|&byte| byte != 0) {
+        return Err(ValidationError::NonNulPadding {
+            position: found_last + padding,
+        });
+    }
+
+    for index in 0..strings {
+        check_bounds::<NULL_PADDED, TERMINATOR>(bytes, index, &offset_at)?;
+    }
+    Ok(())
+}
+
+/// Checks that every string of a structurally valid table is valid UTF-8,
+/// reading its `offsets_len` offsets through `offset_at`.
+fn check_utf8<const NULL_PADDED: bool, const TERMINATOR: u8>(
+    bytes: &[u8],
+    offsets_len: usize,
+    offset_at: impl Fn(usize) -> usize,
+) -> ValidationResult<()> {
+    // With an ASCII terminator, a valid buffer whose offsets all fall on
+    // character boundaries splits into valid strings. Terminators and
+    // alignment padding are ASCII, so they are boundaries too.
+    if !NULL_PADDED || TERMINATOR.is_ascii() {
+        if let Ok(text) = str::from_utf8(bytes) {
+            if (0..offsets_len).all(|index| text.is_char_boundary(offset_at(index))) {
+                return Ok(());
+            }
+        }
+    }
+
+    for index in 0..offsets_len.saturating_sub(1) {
+        let start = offset_at(index);
+        let end = offset_at(index + 1) - usize::from(NULL_PADDED);
+        if str::from_utf8(&bytes[start..end]).is_err() {
+            return Err(ValidationError::InvalidUtf8 { index });
+        }
+    }
+    Ok(())
+}
+
+/// Checks string `index`, whose start and end offsets are read through
+/// `offset_at`, without assuming any table invariant.
+fn check_entry<const NULL_PADDED: bool, const TERMINATOR: u8>(
+    bytes: &[u8],
+    index: usize,
+    offset_at: impl Fn(usize) -> usize,
+) -> ValidationResult<&str> {
+    let range = check_bounds::<NULL_PADDED, TERMINATOR>(bytes, index, offset_at)?;
+    str::from_utf8(&bytes[range]).map_err(|_| ValidationError::InvalidUtf8 { index })
+}
+
+/// Checks the offsets and terminator of string `index` without assuming any
+/// table invariant, returning its logical byte range.
+fn check_bounds<const NULL_PADDED: bool, const TERMINATOR: u8>(
+    bytes: &[u8],
+    index: usize,
+    offset_at: impl Fn(usize) -> usize,
+) -> ValidationResult<Range<usize>> {
+    let bytes_len = bytes.len();
+    let previous = offset_at(index);
+    let current = offset_at(index + 1);
+
+    for (position, offset) in [(index, previous), (index + 1, current)] {
+        if offset > bytes_len {
+            return Err(ValidationError::OffsetOutOfBounds {
+                index: position,
+                offset,
+                bytes_len,
+            });
+        }
+    }
+
+    if current < previous {
+        return Err(ValidationError::OffsetsNotMonotonic {
+            index: index + 1,
+            previous,
+            current,
+        });
+    }
+
+    let mut end = current;
+    if NULL_PADDED {
+        if current == previous {
+            return Err(ValidationError::NullPaddedStringMissingTerminatorByte { index });
+        }
+
+        end = current - 1;
+        if bytes[end] != TERMINATOR {
+            return Err(ValidationError::NullPaddedStringMissingTrailingNul { index });
+        }
+    }
+
+    Ok(previous..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocator::{Global, Vec};
+    use crate::error::{ValidationError, ValidationResult};
+    use crate::{Offset, StringIndex, StringTable};
+
+    fn validate_parts<O: Offset, I: StringIndex, const NULL_PADDED: bool>(
+        bytes: Vec<u8, Global>,
+        offsets: Vec<O, Global>,
+    ) -> ValidationResult<()> {
+        let table = StringTable::<O, I, NULL_PADDED, Global>::from_parts_unchecked(
+            bytes.into_boxed_slice(),
+            offsets.into_boxed_slice(),
+        );
+        table.validate()
+    }
+
+    fn unchecked_table(bytes: &[u8], offsets: &[u32]) -> StringTable {
+        let mut owned_bytes = Vec::new_in(Global);
+        owned_bytes.extend_from_slice(bytes);
+        let mut owned_offsets = Vec::new_in(Global);
+        owned_offsets.extend_from_slice(offsets);
+        StringTable::from_parts_unchecked(
+            owned_bytes.into_boxed_slice(),
+            owned_offsets.into_boxed_slice(),
+        )
+    }
+
+    #[test]
+    fn validate_structure_skips_utf8() {
+        let table = unchecked_table(b"ok\xFF", &[0, 2, 3]);
+        assert_eq!(table.validate_structure(), Ok(()));
+        assert_eq!(
+            table.validate_utf8(),
+            Err(ValidationError::InvalidUtf8 { index: 1 })
+        );
+
+        let table = unchecked_table(b"ab", &[0, 3]);
+        assert!(table.validate_structure().is_err());
+    }
+
+    #[test]
+    fn validate_utf8_rejects_split_characters() {
+        // The buffer is valid UTF-8, but the boundary splits "\u{e9}".
+        let table = unchecked_table("a\u{e9}".as_bytes(), &[0, 2, 3]);
+        assert_eq!(
+            table.validate_utf8(),
+            Err(ValidationError::InvalidUtf8 { index: 0 })
+        );
+        assert_eq!(table.validate(), table.validate_utf8());
+    }
+
+    #[test]
+    fn iter_checked_yields_until_first_bad_entry() {
+        let table = unchecked_table(b"abcd", &[0, 1, 3, 2, 4]);
+        let mut iter = table.iter_checked();
+        assert_eq!(iter.next(), Some(Ok("a")));
+        assert_eq!(iter.next(), Some(Ok("bc")));
+        assert_eq!(
+            iter.next(),
+            Some(Err(ValidationError::OffsetsNotMonotonic {
+                index: 3,
+                previous: 3,
+                current: 2,
+            }))
+        );
+        assert_eq!(iter.next(), None);
+
+        let table = unchecked_table(b"ab", &[0, 2, 9]);
+        assert_eq!(
+            table.iter_checked().nth(1),
+            Some(Err(ValidationError::OffsetOutOfBounds {
+                index: 2,
+                offset: 9,
+                bytes_len: 2,
+            }))
+        );
+
+        let table = unchecked_table(b"", &[]);
+        assert!(table
+            .iter_checked()
+            .eq([Err(ValidationError::MissingSentinelOffset)]));
+        assert!(unchecked_table(b"ok", &[0, 2])
+            .iter_checked()
+            .eq([Ok("ok")]));
+    }
+
+    #[test]
+    fn validate_rejects_sentinel_past_end() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"hello");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(9u32);
+
+        let result = validate_parts::<u32, u32, false>(bytes, offsets);
+        assert_eq!(
+            result,
+            Err(ValidationError::LastOffsetMismatch {
+                found: 9,
+                expected: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_non_monotonic_offsets() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abcd");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(3u32);
+        offsets.push(2u32);
+        offsets.push(4u32);
+
+        let result = validate_parts::<u32, u32, false>(bytes, offsets);
+        assert!(matches!(
+            result,
+            Err(ValidationError::OffsetsNotMonotonic { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_utf8() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.push(0xFF);
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(1u32);
+
+        let result = validate_parts::<u32, u32, false>(bytes, offsets);
+        assert!(matches!(result, Err(ValidationError::InvalidUtf8 { .. })));
+    }
+
+    #[test]
+    fn validate_accepts_nul_padding_past_sentinel() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abc\0\0\0");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(3u32);
+
+        let result = validate_parts::<u32, u32, false>(bytes, offsets);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_non_nul_padding() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abc\0x\0");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(3u32);
+
+        let result = validate_parts::<u32, u32, false>(bytes, offsets);
+        assert_eq!(result, Err(ValidationError::NonNulPadding { position: 4 }));
+    }
+
+    #[test]
+    fn validate_null_padded_accepts_trailing_nul() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"hello\0");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(6u32);
+
+        let result = validate_parts::<u32, u32, true>(bytes, offsets);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_null_padded_rejects_missing_trailing_nul() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"hello");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u32);
+        offsets.push(5u32);
+
+        let result = validate_parts::<u32, u32, true>(bytes, offsets);
+        assert!(matches!(
+            result,
+            Err(ValidationError::NullPaddedStringMissingTrailingNul { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_checks_custom_terminator() {
+        let parts = |bytes: &[u8]| {
+            let mut owned = Vec::new_in(Global);
+            owned.extend_from_slice(bytes);
+            let mut offsets = Vec::new_in(Global);
+            offsets.extend_from_slice(&[0u32, 3, 6]);
+            StringTable::<u32, u16, true, Global, b'\n'>::from_parts_unchecked(
+                owned.into_boxed_slice(),
+                offsets.into_boxed_slice(),
+            )
+        };
+
+        assert!(parts(b"ab\ncd\n").validate().is_ok());
+        assert_eq!(
+            parts(b"ab\ncd\0").validate(),
+            Err(ValidationError::NullPaddedStringMissingTrailingNul { index: 1 })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_offset_type_overflow() {
+        let mut bytes = Vec::new_in(Global);
+        bytes.extend_from_slice(b"abc");
+
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u8);
+        offsets.push(3u8);
+
+        let result = validate_parts::<u8, u32, false>(bytes, offsets);
+        assert!(result.is_ok());
+
+        let mut too_big = Vec::new_in(Global);
+        too_big.extend_from_slice(&[0u8; 300]);
+        let mut offsets = Vec::new_in(Global);
+        offsets.push(0u8);
+        offsets.push(u8::MAX);
+
+        let result = validate_parts::<u8, u32, false>(too_big, offsets);
+        assert!(matches!(
+            result,
+            Err(ValidationError::TooManyBytesForOffsetType { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_id_type_overflow() {
+        let bytes = Vec::new_in(Global).into_boxed_slice();
+        let mut offsets = Vec::new_in(Global);
+        for _ in 0..258 {
+            offsets.push(0u32);
+        }
+
+        let table = StringTable::<u32, u8>::from_parts_unchecked(bytes, offsets.into_boxed_slice());
+        let result = table.validate();
+        assert!(matches!(
+            result,
+            Err(ValidationError::TooManyStrings {
+                strings: 257,
+                id_type: "u8"
+            })
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+//! Unsigned LEB128 varints shared by the compact encodings.
+
+use crate::allocator::*;
+use crate::{Error, Result};
+
+/// Appends `value` as an unsigned LEB128 varint.
+#[inline]
+pub(super) fn write_varint<A: Allocator>(out: &mut Vec<u8, A>, mut value: usize) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Returns the number of bytes [`write_varint`] emits for `value`.
+#[inline]
+pub(super) fn varint_len(value: usize) -> usize {
+    let bits = (usize::BITS - value.leading_zeros()).max(1);
+    bits.div_ceil(7) as usize
+}
+
+/// Reads an unsigned LEB128 varint at `*position`, advancing past it.
+#[inline]
+pub(super) fn read_varint(blob: &[u8], position: &mut usize) -> Result<usize> {
+    let start = *position;
+    let mut value = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = *blob.get(*position).ok_or(Error::InvalidEncoding {
+            position: start,
+            reason: "truncated varint",
+        })?;
+        *position += 1;
+
+        let bits = usize::from(byte & 0x7F);
+        if shift >= usize::BITS || (bits << shift) >> shift != bits {
+            return Err(Error::InvalidEncoding {
+                position: start,
+                reason: "varint does not fit in usize",
+            });
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
@@ -0,0 +1,77 @@
+//! String IDs tagged with the table that issued them.
+//!
+//! In debug builds every builder and table carries a process-unique tag, and
+//! [`BrandedStringId`] remembers the tag of the table it came from, so
+//! [`crate::StringTable::get_branded`] can catch IDs used with the wrong
+//! table. In release builds the tag is zero-sized and the check disappears.
+
+use super::{StringId, StringIndex};
+
+/// Identity of one builder and the table built from it.
+///
+/// Zero-sized unless debug assertions are on and the target has 32-bit
+/// atomics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TableTag {
+    #[cfg(all(debug_assertions, target_has_atomic = "32"))]
+    id: u32,
+}
+
+impl TableTag {
+    /// Returns a tag no other table created so far has.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        #[cfg(all(debug_assertions, target_has_atomic = "32"))]
+        {
+            use core::sync::atomic::{AtomicU32, Ordering};
+
+            static NEXT: AtomicU32 = AtomicU32::new(0);
+            Self {
+                id: NEXT.fetch_add(1, Ordering::Relaxed),
+            }
+        }
+        #[cfg(not(all(debug_assertions, target_has_atomic = "32")))]
+        {
+            Self {}
+        }
+    }
+}
+
+/// A [`StringId`] that remembers which table issued it.
+///
+/// Returned by [`crate::StringTableBuilder::try_push_branded`] and
+/// [`crate::StringTable::brand`], and accepted by
+/// [`crate::StringTable::get_branded`], which debug-asserts that the ID
+/// belongs to that table. In release builds it is the same size as
+/// [`StringId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BrandedStringId<I = u16> {
+    id: StringId<I>,
+    tag: TableTag,
+}
+
+impl<I: StringIndex> BrandedStringId<I> {
+    #[inline]
+    pub(crate) fn new(id: StringId<I>, tag: TableTag) -> Self {
+        Self { id, tag }
+    }
+
+    /// Returns the plain [`StringId`], dropping the brand.
+    #[inline]
+    #[must_use]
+    pub fn id(self) -> StringId<I> {
+        self.id
+    }
+
+    #[inline]
+    pub(crate) fn tag(self) -> TableTag {
+        self.tag
+    }
+}
+
+impl<I: StringIndex> From<BrandedStringId<I>> for StringId<I> {
+    #[inline]
+    fn from(value: BrandedStringId<I>) -> Self {
+        value.id
+    }
+}
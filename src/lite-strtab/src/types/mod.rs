@@ -1,9 +1,12 @@
 //! Public data and type definitions used by the crate.
 
+mod branded_id;
 mod offset;
 mod string_id;
 mod string_index;
 
+pub use branded_id::BrandedStringId;
+pub(crate) use branded_id::TableTag;
 pub use offset::Offset;
 pub use string_id::StringId;
 pub use string_index::StringIndex;
@@ -1,7 +1,7 @@
 //! Backing integer types for byte offsets in [`crate::StringTable`].
 //!
 //! Offsets index into the contiguous UTF-8 byte buffer and include a final
-//! sentinel equal to the total byte length. The chosen [`Offset`] type
+//! sentinel marking the end of the string data. The chosen [`Offset`] type
 //! controls offset-table memory use and maximum total byte size.
 //!
 //! Default [`u32`] supports up to 4 GiB of string bytes.
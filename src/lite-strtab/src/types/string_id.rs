@@ -37,6 +37,42 @@ impl<I: StringIndex> StringId<I> {
     pub fn into_usize(self) -> usize {
         self.0.to_usize()
     }
+
+    /// Returns the value as [`usize`].
+    ///
+    /// Alias of [`Self::into_usize`].
+    #[inline]
+    pub fn index(self) -> usize {
+        self.into_usize()
+    }
+
+    /// Returns the ID `n` positions after this one, or [`None`] if it does not
+    /// fit in `I`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lite_strtab::StringId;
+    ///
+    /// assert_eq!(StringId::<u8>::new(1).checked_add(2), Some(StringId::new(3)));
+    /// assert_eq!(StringId::<u8>::new(255).checked_add(1), None);
+    /// ```
+    #[inline]
+    pub fn checked_add(self, n: usize) -> Option<Self> {
+        self.index()
+            .checked_add(n)
+            .and_then(I::try_from_usize)
+            .map(Self)
+    }
+
+    /// Returns the ID `n` positions before this one, or [`None`] on underflow.
+    #[inline]
+    pub fn checked_sub(self, n: usize) -> Option<Self> {
+        self.index()
+            .checked_sub(n)
+            .and_then(I::try_from_usize)
+            .map(Self)
+    }
 }
 
 impl StringId<u32> {
@@ -47,6 +83,49 @@ impl StringId<u32> {
     }
 }
 
+/// Implements `const` index conversions for IDs backed by primitive integers.
+///
+/// Trait methods cannot be `const` yet, so these mirror
+/// [`StringId::into_usize`] for each concrete type.
+macro_rules! impl_const_string_id {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl StringId<$ty> {
+                /// Returns the value as [`usize`] in `const` contexts.
+                ///
+                /// Same as [`Self::into_usize`].
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// use lite_strtab::StringId;
+                ///
+                #[doc = concat!(
+                    "const INDEX: usize = StringId::<",
+                    stringify!($ty),
+                    ">::new(7).as_usize_const();"
+                )]
+                /// assert_eq!(INDEX, 7);
+                /// ```
+                #[inline]
+                #[must_use]
+                pub const fn as_usize_const(self) -> usize {
+                    self.0 as usize
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(target_pointer_width = "64")]
+impl_const_string_id!(u8, u16, u32, u64, usize);
+
+#[cfg(target_pointer_width = "32")]
+impl_const_string_id!(u8, u16, u32, usize);
+
+#[cfg(target_pointer_width = "16")]
+impl_const_string_id!(u8, u16, usize);
+
 impl<I> From<I> for StringId<I> {
     #[inline]
     fn from(value: I) -> Self {
@@ -7,6 +7,8 @@
 //!
 //! Default [`u16`] supports up to 65_536 strings per table.
 
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
 /// Contract for integer types used by [`crate::StringId`].
 ///
 /// [`Self::try_from_usize`] is used at build and validation boundaries where
@@ -16,7 +18,8 @@
 /// # Implementing this trait
 ///
 /// This trait is already implemented for primitive unsigned integers (`u8`, `u16`, `u32`,
-/// `u64`, `usize`). To implement it for custom wrapper types, use the
+/// `u64`, `usize`) and their [`core::num::NonZero`] counterparts. To implement
+/// it for custom wrapper types, use the
 /// [`impl_string_index`](crate::impl_string_index) macro:
 ///
 /// ```
@@ -108,6 +111,44 @@ crate::impl_string_index!(u8, u16, u32, usize);
 #[cfg(target_pointer_width = "16")]
 crate::impl_string_index!(u8, u16, usize);
 
+/// Implements [`StringIndex`] for [`core::num::NonZero`] integer types.
+///
+/// Raw values are offset by one: index `0` is stored as `1`, so the largest
+/// representable index is one less than for the plain integer. In exchange,
+/// the zero niche lets `Option<StringId<NonZeroU32>>` be the same size as
+/// [`u32`].
+macro_rules! impl_nonzero_string_index {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl StringIndex for $ty {
+                const TYPE_NAME: &'static str = stringify!($ty);
+
+                #[inline]
+                fn try_from_usize(value: usize) -> Option<Self> {
+                    let raw = value.checked_add(1)?;
+                    Self::new(core::convert::TryFrom::try_from(raw).ok()?)
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    // Lossless: the inner type is at most as wide as `usize` on
+                    // every target it is implemented for.
+                    self.get() as usize - 1
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(target_pointer_width = "64")]
+impl_nonzero_string_index!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize);
+
+#[cfg(target_pointer_width = "32")]
+impl_nonzero_string_index!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroUsize);
+
+#[cfg(target_pointer_width = "16")]
+impl_nonzero_string_index!(NonZeroU8, NonZeroU16, NonZeroUsize);
+
 // Supported pointer widths for this crate.
 #[cfg(not(any(
     target_pointer_width = "16",
@@ -115,3 +156,42 @@ crate::impl_string_index!(u8, u16, usize);
     target_pointer_width = "64"
 )))]
 compile_error!("lite-strtab requires a 16-bit, 32-bit, or 64-bit target");
+
+#[cfg(test)]
+mod tests {
+    use core::mem::size_of;
+    use core::num::{NonZeroU32, NonZeroU8};
+
+    use super::StringIndex;
+    use crate::{StringId, StringTableBuilder};
+
+    #[test]
+    fn nonzero_index_is_offset_by_one() {
+        let first = NonZeroU8::try_from_usize(0).unwrap();
+        assert_eq!(first.get(), 1);
+        assert_eq!(first.to_usize(), 0);
+
+        let last = NonZeroU8::try_from_usize(254).unwrap();
+        assert_eq!(last.get(), u8::MAX);
+        assert_eq!(last.to_usize(), 254);
+        assert!(NonZeroU8::try_from_usize(255).is_none());
+        assert!(NonZeroU32::try_from_usize(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn nonzero_ids_round_trip_through_table() {
+        assert_eq!(size_of::<Option<StringId<NonZeroU32>>>(), size_of::<u32>());
+
+        let mut builder = StringTableBuilder::<u32, NonZeroU32>::new_in(crate::Global);
+        let a = builder.try_push("a").unwrap();
+        let b = builder.try_push("b").unwrap();
+        assert_eq!(a.into_raw().get(), 1);
+        assert_eq!(b.into_usize(), 1);
+
+        let table = builder.build();
+        assert_eq!(table.get(a), Some("a"));
+        assert_eq!(table.get(b), Some("b"));
+        assert_eq!(b.checked_sub(1), Some(a));
+        assert_eq!(a.checked_sub(1), None);
+    }
+}
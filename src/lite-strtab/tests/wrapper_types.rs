@@ -1,4 +1,4 @@
-//! Compilation test for custom wrapper types implementing StringIndex and Offset.
+//! Tests for custom wrapper types implementing StringIndex and Offset.
 
 use lite_strtab::{impl_offset, impl_string_index, StringId, StringTableBuilder};
 
@@ -41,3 +41,20 @@ fn _compilation_test() {
     let _id3: StringId<ProviderIdx> = builder3.try_push("test").unwrap();
     let _table3 = builder3.build();
 }
+
+#[test]
+fn string_id_arithmetic_with_wrapper_index() {
+    let first: StringId<ProviderIdx> = StringId::new(ProviderIdx(0));
+
+    let third = first.checked_add(2).unwrap();
+    assert_eq!(third.index(), 2);
+    assert_eq!(third.checked_sub(2).unwrap().index(), 0);
+    assert!(first.checked_sub(1).is_none());
+
+    let last: StringId<ProviderIdx> = StringId::new(ProviderIdx(u16::MAX));
+    assert!(last.checked_add(1).is_none());
+    assert_eq!(
+        last.checked_sub(1).unwrap().index(),
+        usize::from(u16::MAX) - 1
+    );
+}